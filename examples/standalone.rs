@@ -0,0 +1,84 @@
+//! A plain AppKit window with glass, no Electron or Tauri involved
+//!
+//! Run on macOS with `cargo run --example standalone`; quit with Cmd+Q.
+
+#[cfg(target_os = "macos")]
+fn main() {
+    use cocoa::appkit::{
+        NSApp, NSApplication, NSApplicationActivationPolicy, NSBackingStoreType, NSMenu,
+        NSMenuItem, NSWindow, NSWindowStyleMask,
+    };
+    use cocoa::base::{id, nil, NO, YES};
+    use cocoa::foundation::{NSAutoreleasePool, NSPoint, NSRect, NSSize, NSString};
+    use liquid_glass_rs::{GlassMaterialVariant, GlassOptions, GlassViewManager};
+    use objc::{sel, sel_impl};
+    use std::ffi::c_void;
+
+    unsafe {
+        let _pool = NSAutoreleasePool::new(nil);
+
+        let app = NSApp();
+        app.setActivationPolicy_(NSApplicationActivationPolicy::NSApplicationActivationPolicyRegular);
+
+        // Minimal menu so Cmd+Q quits
+        let menubar = NSMenu::new(nil);
+        let app_menu_item = NSMenuItem::new(nil);
+        menubar.addItem_(app_menu_item);
+        app.setMainMenu_(menubar);
+        let app_menu = NSMenu::new(nil);
+        let quit_item = NSMenuItem::alloc(nil).initWithTitle_action_keyEquivalent_(
+            NSString::alloc(nil).init_str("Quit"),
+            sel!(terminate:),
+            NSString::alloc(nil).init_str("q"),
+        );
+        app_menu.addItem_(quit_item);
+        app_menu_item.setSubmenu_(app_menu);
+
+        let style = NSWindowStyleMask::NSTitledWindowMask
+            | NSWindowStyleMask::NSClosableWindowMask
+            | NSWindowStyleMask::NSResizableWindowMask
+            | NSWindowStyleMask::NSFullSizeContentViewWindowMask;
+        let window: id = NSWindow::alloc(nil).initWithContentRect_styleMask_backing_defer_(
+            NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(480.0, 320.0)),
+            style,
+            NSBackingStoreType::NSBackingStoreBuffered,
+            NO,
+        );
+        window.center();
+        window.setTitle_(NSString::alloc(nil).init_str("liquid-glass-rs"));
+        window.setTitlebarAppearsTransparent_(YES);
+
+        let manager = GlassViewManager::new();
+        if !manager.is_supported() {
+            eprintln!("Native glass is not available; using the NSVisualEffectView fallback");
+        }
+
+        manager
+            .prepare_window_for_glass(window as *mut c_void)
+            .expect("failed to prepare window");
+
+        let options = GlassOptions {
+            corner_radius: 12.0,
+            tint_color: Some("#4080ff30".to_string()),
+            ..Default::default()
+        };
+        let content_view = window.contentView();
+        let view_id = manager
+            .add_glass_view(content_view as *mut c_void, options)
+            .expect("failed to add glass view");
+
+        // Variants are private API, so this can fail on the fallback
+        if let Err(err) = manager.set_variant(view_id, GlassMaterialVariant::Dock) {
+            eprintln!("Could not set variant: {}", err);
+        }
+
+        window.makeKeyAndOrderFront_(nil);
+        app.activateIgnoringOtherApps_(YES);
+        app.run();
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn main() {
+    eprintln!("This example requires macOS");
+}
@@ -0,0 +1,208 @@
+//! Color parsing and formatting for tints
+
+use crate::error::{GlassError, Result};
+
+/// Dark text for light tints, slightly softer than pure black
+const DARK_TEXT: &str = "#1d1d1f";
+/// Light text for dark tints, slightly softer than pure white
+const LIGHT_TEXT: &str = "#f5f5f7";
+
+/// Pick a readable text color for labels over a tint
+///
+/// Returns near-black or near-white as `#RRGGBB`, whichever has the higher
+/// WCAG contrast ratio against the tint. The tint's alpha is ignored, and
+/// tints that cannot be parsed get the dark color.
+pub fn recommended_text_color(tint: &str) -> String {
+    let Ok((r, g, b, _)) = parse_hex_rgba(tint) else {
+        return DARK_TEXT.to_string();
+    };
+    let background = relative_luminance(r, g, b);
+
+    let contrast = |text: &str| {
+        let (r, g, b, _) = parse_hex_rgba(text).unwrap();
+        let text = relative_luminance(r, g, b);
+        (background.max(text) + 0.05) / (background.min(text) + 0.05)
+    };
+    if contrast(DARK_TEXT) >= contrast(LIGHT_TEXT) {
+        DARK_TEXT.to_string()
+    } else {
+        LIGHT_TEXT.to_string()
+    }
+}
+
+/// WCAG relative luminance of a gamma-encoded sRGB color
+fn relative_luminance(r: f64, g: f64, b: f64) -> f64 {
+    let linear = |c: f64| {
+        if c <= 0.040_45 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// Mix a color toward the grey of the same luminance by `amount` (0..=1)
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn desaturate((r, g, b, a): (f64, f64, f64, f64), amount: f64) -> (f64, f64, f64, f64) {
+    let grey = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let amount = amount.clamp(0.0, 1.0);
+    let mix = |c: f64| c + (grey - c) * amount;
+    (mix(r), mix(g), mix(b), a)
+}
+
+/// Move a tint away from the backdrop's luminance for contrast
+///
+/// Over a bright backdrop the tint is mixed toward black, over a dark one
+/// toward white; `contrast` (0..=1) scales how far.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn adapt_tint(
+    (r, g, b, a): (f64, f64, f64, f64),
+    luminance: f64,
+    contrast: f64,
+) -> (f64, f64, f64, f64) {
+    let amount = (contrast.clamp(0.0, 1.0) * (luminance.clamp(0.0, 1.0) - 0.5) * 2.0).abs();
+    let target = if luminance > 0.5 { 0.0 } else { 1.0 };
+    let mix = |c: f64| c + (target - c) * amount;
+    (mix(r), mix(g), mix(b), a)
+}
+
+/// Parse a `#RRGGBB` or `#RRGGBBAA` string into sRGB components
+///
+/// CSS `oklch()` and `oklab()` colors are accepted too and converted to sRGB.
+pub(crate) fn parse_hex_rgba(hex: &str) -> Result<(f64, f64, f64, f64)> {
+    if let Some(rgba) = parse_ok_color(hex) {
+        return rgba;
+    }
+
+    let cleaned = hex.trim().trim_start_matches('#');
+
+    if cleaned.len() != 6 && cleaned.len() != 8 {
+        return Err(GlassError::InvalidColor(hex.to_string()));
+    }
+
+    let rgba = u32::from_str_radix(cleaned, 16)
+        .map_err(|_| GlassError::InvalidColor(hex.to_string()))?;
+
+    let (r, g, b, a) = if cleaned.len() == 6 {
+        (
+            ((rgba >> 16) & 0xFF) as f64 / 255.0,
+            ((rgba >> 8) & 0xFF) as f64 / 255.0,
+            (rgba & 0xFF) as f64 / 255.0,
+            1.0,
+        )
+    } else {
+        (
+            ((rgba >> 24) & 0xFF) as f64 / 255.0,
+            ((rgba >> 16) & 0xFF) as f64 / 255.0,
+            ((rgba >> 8) & 0xFF) as f64 / 255.0,
+            (rgba & 0xFF) as f64 / 255.0,
+        )
+    };
+
+    Ok((r, g, b, a))
+}
+
+/// Format sRGB components as `#RRGGBBAA`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn format_hex_rgba((r, g, b, a): (f64, f64, f64, f64)) -> String {
+    let byte = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}{:02x}", byte(r), byte(g), byte(b), byte(a))
+}
+
+/// Parse `oklab(L a b [/ alpha])` or `oklch(L C H [/ alpha])` into sRGB `(r, g, b, a)`
+///
+/// Returns `None` if `color` is not one of these functions, so callers can
+/// try other formats. Follows CSS Color 4: `L` is 0..1 or a percentage, `a`,
+/// `b` and `C` take percentages of 0.4, hues are in degrees unless given a
+/// unit, and `none` means 0. Colors outside the sRGB gamut are clamped per
+/// channel.
+pub(crate) fn parse_ok_color(color: &str) -> Option<Result<(f64, f64, f64, f64)>> {
+    let trimmed = color.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    let (polar, args) = if let Some(args) = lower.strip_prefix("oklch(") {
+        (true, args)
+    } else if let Some(args) = lower.strip_prefix("oklab(") {
+        (false, args)
+    } else {
+        return None;
+    };
+
+    let invalid = || GlassError::InvalidColor(color.to_string());
+    let parse = || -> Option<(f64, f64, f64, f64)> {
+        let args = args.strip_suffix(')')?;
+        let (channels, alpha) = match args.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (args, None),
+        };
+        let channels: Vec<&str> = channels.split_whitespace().collect();
+        let [lightness, x, y] = channels[..] else {
+            return None;
+        };
+
+        let lightness = parse_component(lightness, 1.0)?;
+        let (a, b) = if polar {
+            let chroma = parse_component(x, 0.4)?;
+            let hue = parse_hue(y)?.to_radians();
+            (chroma * hue.cos(), chroma * hue.sin())
+        } else {
+            (parse_component(x, 0.4)?, parse_component(y, 0.4)?)
+        };
+        let alpha = match alpha {
+            Some(alpha) => parse_component(alpha, 1.0)?.clamp(0.0, 1.0),
+            None => 1.0,
+        };
+
+        let (r, g, b) = oklab_to_srgb(lightness, a, b);
+        Some((r, g, b, alpha))
+    };
+
+    Some(parse().ok_or_else(invalid))
+}
+
+/// Parse a number or percentage, where 100% is `percent_scale`
+fn parse_component(value: &str, percent_scale: f64) -> Option<f64> {
+    if value == "none" {
+        return Some(0.0);
+    }
+    let number = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok()? / 100.0 * percent_scale,
+        None => value.parse::<f64>().ok()?,
+    };
+    number.is_finite().then_some(number)
+}
+
+/// Parse a hue angle into degrees
+fn parse_hue(value: &str) -> Option<f64> {
+    if value == "none" {
+        return Some(0.0);
+    }
+    let units = [("deg", 1.0), ("grad", 0.9), ("rad", 180.0 / std::f64::consts::PI), ("turn", 360.0)];
+    let (number, scale) = units
+        .iter()
+        .find_map(|&(unit, scale)| value.strip_suffix(unit).map(|number| (number, scale)))
+        .unwrap_or((value, 1.0));
+    let degrees = number.parse::<f64>().ok()? * scale;
+    degrees.is_finite().then_some(degrees)
+}
+
+/// Convert OKLab to gamma-encoded sRGB, clamped to 0..=1
+fn oklab_to_srgb(lightness: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let l = (lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+    let m = (lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+    let s = (lightness - 0.089_484_177_5 * a - 1.291_485_548_0 * b).powi(3);
+
+    let red = 4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s;
+    let green = -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s;
+    let blue = -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s;
+
+    let encode = |c: f64| {
+        let c = c.clamp(0.0, 1.0);
+        if c <= 0.003_130_8 {
+            12.92 * c
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    (encode(red), encode(green), encode(blue))
+}
@@ -0,0 +1,68 @@
+//! Single-use completion signal bridging native callbacks to futures
+
+use crate::error::{GlassError, Result};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Default)]
+struct State {
+    done: bool,
+    dropped: bool,
+    waker: Option<Waker>,
+}
+
+/// Completes the paired [`Completion`] when called or dropped
+pub(crate) struct Completer {
+    state: Arc<Mutex<State>>,
+}
+
+/// Future resolving once the paired [`Completer`] fires
+pub struct Completion {
+    state: Arc<Mutex<State>>,
+}
+
+/// Create a connected completer and future
+pub(crate) fn completion() -> (Completer, Completion) {
+    let state = Arc::new(Mutex::new(State::default()));
+    (
+        Completer { state: state.clone() },
+        Completion { state },
+    )
+}
+
+impl Completer {
+    /// Resolve the future successfully
+    pub(crate) fn complete(self) {
+        self.state.lock().unwrap().done = true;
+    }
+}
+
+impl Drop for Completer {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        state.dropped = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Future for Completion {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        if state.done {
+            Poll::Ready(Ok(()))
+        } else if state.dropped {
+            Poll::Ready(Err(GlassError::RuntimeError(
+                "Animation was discarded before completing".to_string(),
+            )))
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
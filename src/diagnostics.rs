@@ -0,0 +1,96 @@
+//! Diagnostics helpers for reproducing lifecycle issues
+
+use crate::error::Result;
+use crate::platform::{GlassOptions, GlassViewManager};
+use std::ffi::c_void;
+
+/// Outcome of [`run_churn_test`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChurnReport {
+    /// Number of add/remove cycles performed
+    pub iterations: usize,
+    /// Views still managed after the test that were not there before
+    pub leaked_views: usize,
+    /// Resident memory before the test, in bytes (if available)
+    pub resident_before: Option<u64>,
+    /// Resident memory after the test, in bytes (if available)
+    pub resident_after: Option<u64>,
+}
+
+impl ChurnReport {
+    /// Growth in resident memory over the test, in bytes
+    ///
+    /// Allocator caching makes small growth normal; growth proportional to
+    /// the iteration count points to a native leak.
+    pub fn resident_growth(&self) -> Option<i64> {
+        Some(self.resident_after? as i64 - self.resident_before? as i64)
+    }
+}
+
+/// Outcome of [`GlassViewManager::self_test`], one flag per capability
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SelfTestReport {
+    /// Whether the view was backed by native glass rather than a fallback
+    ///
+    /// Informational only: the fallback is a working configuration, so this
+    /// does not count toward [`passed`](Self::passed).
+    pub native_glass: bool,
+    /// A glass view was created and attached to the test window
+    pub creation: bool,
+    /// The tint color read back matches the one applied
+    pub tint: bool,
+    /// The material variant read back matches the one set
+    pub variant: bool,
+    /// The scrim state read back matches the one set
+    pub scrim: bool,
+    /// The subdued state read back matches the one set
+    pub subdued: bool,
+    /// The view was removed and detached from the test window
+    pub removal: bool,
+    /// Why each failed check failed
+    pub failures: Vec<String>,
+}
+
+impl SelfTestReport {
+    /// Whether every capability check passed, regardless of `native_glass`
+    pub fn passed(&self) -> bool {
+        self.creation && self.tint && self.variant && self.scrim && self.subdued && self.removal
+    }
+}
+
+/// Repeatedly add and remove a glass view on `handle`
+///
+/// Reports whether the manager returns to its starting view count and how
+/// resident memory changed. Must be called from the main thread.
+pub fn run_churn_test(
+    manager: &GlassViewManager,
+    handle: *mut c_void,
+    iterations: usize,
+) -> Result<ChurnReport> {
+    let views_before = manager.view_count();
+    let resident_before = resident_memory_bytes();
+
+    for _ in 0..iterations {
+        let view_id = manager.add_glass_view(handle, GlassOptions::default())?;
+        manager.remove_view(view_id)?;
+    }
+
+    Ok(ChurnReport {
+        iterations,
+        leaked_views: manager.view_count().saturating_sub(views_before),
+        resident_before,
+        resident_after: resident_memory_bytes(),
+    })
+}
+
+fn resident_memory_bytes() -> Option<u64> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::resident_memory_bytes()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
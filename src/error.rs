@@ -22,10 +22,34 @@ pub enum GlassError {
     #[error("Invalid color format: {0}")]
     InvalidColor(String),
 
+    /// Image decoding error
+    #[error("Invalid image data")]
+    InvalidImage,
+
+    /// Saved state could not be read
+    #[error("Invalid state: {0}")]
+    InvalidState(String),
+
     /// Runtime error from Objective-C
     #[error("Objective-C runtime error: {0}")]
     RuntimeError(String),
 
+    /// The container already has a glass view, under `DedupPolicy::Error`
+    #[error("Container already has glass view {0}")]
+    AlreadyExists(i32),
+
+    /// No Objective-C class is registered under the name
+    #[error("Class {0} is not registered")]
+    ClassNotFound(String),
+
+    /// The class exists but cannot be used for this purpose
+    #[error("Invalid class: {0}")]
+    InvalidClass(String),
+
+    /// No more view IDs are available
+    #[error("View ID limit exceeded")]
+    LimitExceeded,
+
     /// View creation failed
     #[error("Failed to create glass view")]
     CreationFailed,
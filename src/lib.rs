@@ -18,7 +18,7 @@
 //! let options = GlassOptions {
 //!     corner_radius: 16.0,
 //!     tint_color: Some("#FF0000AA".to_string()),
-//!     opaque: false,
+//!     ..Default::default()
 //! };
 //!
 //! // window_ptr is a pointer to NSView from Electron
@@ -27,14 +27,32 @@
 
 #![warn(missing_docs)]
 
+mod color;
+#[cfg(feature = "async")]
+mod completion;
+pub mod diagnostics;
 mod error;
 mod platform;
+pub mod prelude;
+mod registry;
 
 #[cfg(target_os = "macos")]
 mod macos;
 
+#[cfg(feature = "async")]
+pub use completion::Completion;
+pub use color::recommended_text_color;
 pub use error::{GlassError, Result};
-pub use platform::{GlassOptions, GlassViewManager, GlassMaterialVariant};
+pub use registry::{GlassRegistry, WindowScope};
+pub use platform::{
+    AccessibilityPolicy, Appearance, AttachPosition, BatchTarget, BlendingMode, CornerMask,
+    DedupPolicy, FootprintEstimate, GlassBackend, GlassBatch, GlassCapabilities,
+    GlassMaterialVariant, GlassOptions, GlassViewHandle, GlassViewManager, Orientation, PinOptions,
+    Rect, RenderingEnvironment, Shadow, TintBlendMode, TintLayer, TintTiming, TransactionScope,
+    VersionInfo, ViewInfo,
+};
+#[cfg(feature = "serde")]
+pub use platform::ViewState;
 
 #[cfg(test)]
 mod tests {
@@ -51,5 +69,142 @@ mod tests {
         let opts = GlassOptions::default();
         assert_eq!(opts.corner_radius, 0.0);
         assert_eq!(opts.opaque, false);
+        assert_eq!(opts.round_corners, CornerMask::all());
+    }
+
+    #[test]
+    fn test_glass_options_normalized() {
+        let opts = GlassOptions {
+            corner_radius: -4.0,
+            background_corner_radius: Some(-1.0),
+            aspect_ratio: Some(0.0),
+            grain_intensity: Some(1.5),
+            ..Default::default()
+        }
+        .normalized();
+        assert_eq!(opts.corner_radius, 0.0);
+        assert_eq!(opts.background_corner_radius, Some(0.0));
+        assert_eq!(opts.aspect_ratio, None);
+        assert_eq!(opts.grain_intensity, Some(1.0));
+    }
+
+    #[test]
+    fn test_parse_ok_color() {
+        let close = |(r, g, b, a): (f64, f64, f64, f64), expected: (f64, f64, f64, f64)| {
+            [(r, expected.0), (g, expected.1), (b, expected.2), (a, expected.3)]
+                .iter()
+                .all(|(x, y)| (x - y).abs() < 1e-3)
+        };
+
+        let white = color::parse_ok_color("oklch(1 0 0)").unwrap().unwrap();
+        assert!(close(white, (1.0, 1.0, 1.0, 1.0)));
+        let black = color::parse_ok_color("OKLab(0% 0 0 / 50%)").unwrap().unwrap();
+        assert!(close(black, (0.0, 0.0, 0.0, 0.5)));
+        // sRGB red, with the out-of-gamut rounding error clamped away
+        let red = color::parse_ok_color("oklch(0.62796 0.25768 29.2339deg)").unwrap().unwrap();
+        assert!(close(red, (1.0, 0.0, 0.0, 1.0)));
+
+        assert!(color::parse_ok_color("oklch(0.5 0.1)").unwrap().is_err());
+        assert!(color::parse_ok_color("oklab(0.5 x 0").unwrap().is_err());
+        assert!(color::parse_ok_color("#ffffff").is_none());
+    }
+
+    #[test]
+    fn test_recommended_text_color() {
+        assert_eq!(recommended_text_color("#ffffff"), "#1d1d1f");
+        assert_eq!(recommended_text_color("#000000cc"), "#f5f5f7");
+        assert_eq!(recommended_text_color("#ffd60a"), "#1d1d1f");
+        assert_eq!(recommended_text_color("#0a3d91"), "#f5f5f7");
+        assert_eq!(recommended_text_color("not a color"), "#1d1d1f");
+    }
+
+    fn assert_rgba_close(actual: (f64, f64, f64, f64), expected: (f64, f64, f64, f64)) {
+        let close = |x: f64, y: f64| (x - y).abs() < 1e-9;
+        assert!(
+            close(actual.0, expected.0)
+                && close(actual.1, expected.1)
+                && close(actual.2, expected.2)
+                && close(actual.3, expected.3),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_adapt_tint() {
+        let base = (0.2, 0.4, 0.6, 0.5);
+        assert_rgba_close(color::adapt_tint(base, 0.5, 1.0), base);
+        assert_rgba_close(color::adapt_tint(base, 1.0, 0.0), base);
+        assert_rgba_close(color::adapt_tint(base, 1.0, 1.0), (0.0, 0.0, 0.0, 0.5));
+        assert_rgba_close(color::adapt_tint(base, 0.0, 1.0), (1.0, 1.0, 1.0, 0.5));
+        assert_rgba_close(color::adapt_tint(base, 0.0, 0.5), (0.6, 0.7, 0.8, 0.5));
+        assert_rgba_close(color::adapt_tint(base, 0.75, 1.0), (0.1, 0.2, 0.3, 0.5));
+    }
+
+    #[test]
+    fn test_desaturate() {
+        let red = (1.0, 0.0, 0.0, 0.8);
+        assert_rgba_close(color::desaturate(red, 0.0), red);
+        assert_rgba_close(color::desaturate(red, 1.0), (0.2126, 0.2126, 0.2126, 0.8));
+        assert_rgba_close(color::desaturate(red, 2.0), (0.2126, 0.2126, 0.2126, 0.8));
+        assert_rgba_close(color::desaturate(red, 0.5), (0.6063, 0.1063, 0.1063, 0.8));
+    }
+
+    #[test]
+    fn test_glass_batch_targets() {
+        let mut batch = GlassBatch::new();
+        let added = batch.add(std::ptr::null_mut(), GlassOptions::default());
+        batch.set_variant(added, GlassMaterialVariant::Dock).set_scrim_state(7, 1);
+        assert_eq!(added, BatchTarget::Added(0));
+        assert_eq!(batch.len(), 3);
+    }
+
+    #[test]
+    fn test_variant_all() {
+        let all = GlassMaterialVariant::all();
+        assert_eq!(all.len(), 24);
+        assert!(all.iter().enumerate().all(|(i, &variant)| variant.raw() == i as i64));
+    }
+
+    #[test]
+    fn test_variant_from_raw() {
+        assert_eq!(GlassMaterialVariant::from_raw(2), Some(GlassMaterialVariant::Dock));
+        assert_eq!(GlassMaterialVariant::from_raw(23), Some(GlassMaterialVariant::CartouchePopover));
+        assert_eq!(GlassMaterialVariant::from_raw(24), None);
+        assert_eq!(GlassMaterialVariant::Unknown(24).raw(), 24);
+        assert_eq!(GlassMaterialVariant::Unknown(2), GlassMaterialVariant::Dock);
+        assert_ne!(GlassMaterialVariant::Unknown(24), GlassMaterialVariant::Regular);
+    }
+
+    #[cfg(feature = "variant-as-int")]
+    #[test]
+    fn test_variant_serde_as_int() {
+        let json = serde_json::to_string(&GlassMaterialVariant::Dock).unwrap();
+        assert_eq!(json, "2");
+        let parsed: GlassMaterialVariant = serde_json::from_str("16").unwrap();
+        assert_eq!(parsed, GlassMaterialVariant::Sidebar);
+        let unknown: GlassMaterialVariant = serde_json::from_str("99").unwrap();
+        assert_eq!(unknown, GlassMaterialVariant::Unknown(99));
+        assert_eq!(serde_json::to_string(&unknown).unwrap(), "99");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_glass_options_serde_roundtrip() {
+        let opts = GlassOptions {
+            corner_radius: 12.0,
+            round_corners: CornerMask::TOP_LEFT | CornerMask::TOP_RIGHT,
+            tint_color: Some("#FFFFFF80".to_string()),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&opts).unwrap();
+        let parsed: GlassOptions = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.corner_radius, 12.0);
+        assert_eq!(parsed.round_corners, opts.round_corners);
+        assert_eq!(parsed.tint_color, opts.tint_color);
+
+        // Missing fields fall back to their defaults
+        let parsed: GlassOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(parsed.round_corners, CornerMask::all());
     }
 }
@@ -1,306 +1,4621 @@
 //! macOS-specific glass effect implementation using Objective-C runtime
 
+use crate::color::{adapt_tint, desaturate, format_hex_rgba, parse_hex_rgba};
+use crate::diagnostics::SelfTestReport;
 use crate::error::{GlassError, Result};
-use crate::platform::{GlassMaterialVariant, GlassOptions};
+use crate::platform::{
+    AccessibilityPolicy, Appearance, AttachPosition, BlendingMode, CornerMask, DedupPolicy, FootprintEstimate, GlassBackend, GlassMaterialVariant, GlassOptions, GlassViewHandle, Orientation, PinOptions, Rect, RenderingEnvironment, Shadow, TintTiming,
+    ViewInfo,
+};
+#[cfg(feature = "serde")]
+use crate::platform::ViewState;
+use block2::{Block, RcBlock};
 use cocoa::appkit::{NSVisualEffectView, NSColor};
-use cocoa::base::{id, nil, YES};
-use cocoa::foundation::{NSRect, NSString};
-use objc::runtime::{Class, Sel};
+use cocoa::base::{id, nil, NO, YES};
+use cocoa::foundation::{NSPoint, NSRect, NSSize, NSString};
+use objc::declare::ClassDecl;
+use objc::runtime::{object_getClass, Class, Object, Sel, BOOL};
 use objc::{msg_send, sel, sel_impl, class};
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::ffi::c_void;
+use std::rc::Rc;
+use std::sync::{Once, OnceLock};
 
-/// Manager for macOS glass effects
-pub struct MacOSGlassManager {
-    views: HashMap<i32, id>,
-    next_id: i32,
+/// Closures invoked when the pointer enters or leaves a view
+struct HoverCallbacks {
+    on_enter: Box<dyn Fn()>,
+    on_exit: Box<dyn Fn()>,
 }
 
-impl MacOSGlassManager {
-    /// Create a new macOS glass manager
-    pub fn new() -> Self {
+/// Tracking area installed on a view for hover callbacks
+pub struct HoverTracking {
+    area: id,
+    target: id,
+    _callbacks: Box<HoverCallbacks>,
+}
+
+unsafe extern "C" {
+    fn object_setClass(obj: id, cls: *const Class) -> *const Class;
+    fn objc_setAssociatedObject(object: id, key: *const c_void, value: id, policy: usize);
+    fn objc_getAssociatedObject(object: id, key: *const c_void) -> id;
+}
+
+/// `OBJC_ASSOCIATION_RETAIN_NONATOMIC`
+const ASSOCIATION_RETAIN_NONATOMIC: usize = 1;
+
+#[repr(C)]
+#[derive(Default)]
+struct MachTaskBasicInfo {
+    virtual_size: u64,
+    resident_size: u64,
+    resident_size_max: u64,
+    user_time: [i32; 2],
+    system_time: [i32; 2],
+    policy: i32,
+    suspend_count: i32,
+}
+
+const MACH_TASK_BASIC_INFO: u32 = 20;
+
+unsafe extern "C" {
+    static mach_task_self_: u32;
+    fn task_info(task: u32, flavor: u32, info: *mut MachTaskBasicInfo, count: *mut u32) -> i32;
+}
+
+/// Current resident memory of this process in bytes
+pub fn resident_memory_bytes() -> Option<u64> {
+    let mut info = MachTaskBasicInfo::default();
+    let mut count = (std::mem::size_of::<MachTaskBasicInfo>() / std::mem::size_of::<u32>()) as u32;
+    let status = unsafe {
+        task_info(mach_task_self_, MACH_TASK_BASIC_INFO, &mut info, &mut count)
+    };
+    (status == 0).then_some(info.resident_size)
+}
+
+unsafe extern "C" {
+    fn sysctlbyname(
+        name: *const std::ffi::c_char,
+        oldp: *mut c_void,
+        oldlenp: *mut usize,
+        newp: *mut c_void,
+        newlen: usize,
+    ) -> i32;
+}
+
+/// Read an integer sysctl, or `None` if it does not exist
+fn sysctl_int(name: &str) -> Option<i32> {
+    let name = std::ffi::CString::new(name).ok()?;
+    let mut value: i32 = 0;
+    let mut len = std::mem::size_of::<i32>();
+    let status = unsafe {
+        sysctlbyname(name.as_ptr(), &mut value as *mut i32 as *mut c_void, &mut len, std::ptr::null_mut(), 0)
+    };
+    (status == 0).then_some(value)
+}
+
+/// Detect translation and virtualization
+pub fn rendering_environment() -> RenderingEnvironment {
+    if sysctl_int("kern.hv_vmm_present") == Some(1) {
+        RenderingEnvironment::VirtualMachine
+    } else if sysctl_int("sysctl.proc_translated") == Some(1) {
+        RenderingEnvironment::Rosetta
+    } else {
+        RenderingEnvironment::Native
+    }
+}
+
+#[repr(C)]
+struct OperatingSystemVersion {
+    major: isize,
+    minor: isize,
+    patch: isize,
+}
+
+/// Major macOS version of the running system
+fn macos_major_version() -> isize {
+    unsafe {
+        let info: id = msg_send![class!(NSProcessInfo), processInfo];
+        let version: OperatingSystemVersion = msg_send![info, operatingSystemVersion];
+        version.major
+    }
+}
+
+/// Best-effort corner radius of standard titled windows on the running macOS
+pub fn system_window_corner_radius() -> f64 {
+    match macos_major_version() {
+        26.. => 16.0,
+        11.. => 10.0,
+        _ => 5.0,
+    }
+}
+
+/// `GlassOptions` overrides read from `GLASS_DEFAULT_*` environment variables
+#[derive(Debug, Clone, Default)]
+pub struct EnvDefaults {
+    corner_radius: Option<f64>,
+    tint_color: Option<String>,
+    blur_radius: Option<f64>,
+    variant: Option<i64>,
+}
+
+impl EnvDefaults {
+    /// Read the overrides, skipping unset and unparsable variables
+    pub fn from_env() -> Self {
+        fn parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+            let value = std::env::var(name).ok()?;
+            let parsed = value.trim().parse().ok();
+            if parsed.is_none() {
+                log::warn!("Ignoring invalid {}={:?}", name, value);
+            }
+            parsed
+        }
+
         Self {
-            views: HashMap::new(),
-            next_id: 0,
+            corner_radius: parse("GLASS_DEFAULT_CORNER_RADIUS"),
+            tint_color: std::env::var("GLASS_DEFAULT_TINT_COLOR").ok().filter(|value| {
+                let valid = parse_hex_rgba(value).is_ok();
+                if !valid {
+                    log::warn!("Ignoring invalid GLASS_DEFAULT_TINT_COLOR={:?}", value);
+                }
+                valid
+            }),
+            blur_radius: parse("GLASS_DEFAULT_BLUR_RADIUS"),
+            variant: parse("GLASS_DEFAULT_VARIANT"),
         }
     }
 
-    /// Check if glass effects are supported on this macOS version
-    pub fn is_supported(&self) -> bool {
-        // NSGlassEffectView is available on macOS 15+
-        Class::get("NSGlassEffectView").is_some()
+    /// Replace fields left at their default value with the overrides
+    fn apply(&self, mut options: GlassOptions) -> GlassOptions {
+        let defaults = GlassOptions::default();
+        if let Some(radius) = self.corner_radius
+            && options.corner_radius == defaults.corner_radius
+        {
+            options.corner_radius = radius;
+        }
+        if options.tint_color.is_none() {
+            options.tint_color = self.tint_color.clone();
+        }
+        if let Some(radius) = self.blur_radius
+            && options.blur_radius == defaults.blur_radius
+        {
+            options.blur_radius = radius;
+        }
+        options
     }
+}
 
-    /// Add a glass effect view to a window
-    pub fn add_glass_view(
-        &mut self,
-        window_handle: *mut c_void,
-        options: GlassOptions,
-    ) -> Result<i32> {
-        unsafe {
-            // Check main thread
-            let current_thread: id = msg_send![class!(NSThread), currentThread];
-            let is_main: bool = msg_send![current_thread, isMainThread];
-            if !is_main {
-                return Err(GlassError::RuntimeError(
-                    "Must be called from main thread".to_string(),
-                ));
-            }
+/// Normalize options and resolve values that depend on the system
+fn resolve_options(options: GlassOptions) -> GlassOptions {
+    let mut options = options.normalized();
+    if options.match_window_corners {
+        options.corner_radius = system_window_corner_radius();
+    }
+    options
+}
 
-            // Cast the window handle to NSView
-            let root_view = window_handle as id;
-            if root_view.is_null() {
-                return Err(GlassError::InvalidHandle);
-            }
+/// Opaque libdispatch queue
+#[repr(C)]
+struct DispatchQueue {
+    _private: [u8; 0],
+}
 
-            // Get bounds
-            let bounds: NSRect = msg_send![root_view, bounds];
+unsafe extern "C" {
+    static _dispatch_main_q: DispatchQueue;
+    fn dispatch_async_f(
+        queue: *const DispatchQueue,
+        context: *mut c_void,
+        work: extern "C" fn(*mut c_void),
+    );
+}
 
-            // Create background view if opaque
-            let background_view = if options.opaque {
-                Some(self.create_background_view(bounds)?)
-            } else {
-                None
-            };
+/// Run a closure asynchronously on the main queue
+pub fn dispatch_main_async(f: impl FnOnce() + 'static) {
+    extern "C" fn trampoline(context: *mut c_void) {
+        let f = unsafe { Box::from_raw(context as *mut Box<dyn FnOnce()>) };
+        f();
+    }
 
-            // Try to create NSGlassEffectView first, fall back to NSVisualEffectView
-            let glass_view = if let Some(glass_view) = self.create_glass_view(bounds)? {
-                glass_view
-            } else {
-                self.create_fallback_view(bounds)?
-            };
+    let context = Box::into_raw(Box::new(Box::new(f) as Box<dyn FnOnce()>));
+    unsafe {
+        dispatch_async_f(&raw const _dispatch_main_q, context as *mut c_void, trampoline);
+    }
+}
 
-            // Add views to container
-            if let Some(bg) = background_view {
-                self.add_subview(root_view, bg, nil)?;
-            }
+#[link(name = "CoreGraphics", kind = "framework")]
+unsafe extern "C" {
+    fn CGPathCreateWithRoundedRect(
+        rect: NSRect,
+        corner_width: f64,
+        corner_height: f64,
+        transform: *const c_void,
+    ) -> *const c_void;
+    fn CGPathRelease(path: *const c_void);
+}
 
-            let relative_to = background_view.unwrap_or(nil);
-            self.add_subview(root_view, glass_view, relative_to)?;
+/// Width of a separator in points
+const SEPARATOR_THICKNESS: f64 = 1.0;
+/// NSVisualEffectMaterialHeaderView, the closest public material to a separator
+const SEPARATOR_MATERIAL: isize = 10;
 
-            // Configure the glass view
-            self.configure_glass_view(glass_view, &options)?;
+const HOVER_TARGET_CLASS: &str = "LiquidGlassHoverTarget";
+const HOVER_CALLBACKS_IVAR: &str = "_callbacks";
 
-            // Store view ID
-            let view_id = self.next_id;
-            self.next_id += 1;
-            
-            self.views.insert(view_id, glass_view);
+/// Get (registering on first use) the class that receives tracking area events
+fn hover_target_class() -> &'static Class {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        let mut decl = ClassDecl::new(HOVER_TARGET_CLASS, class!(NSObject))
+            .expect("hover target class already registered");
+        decl.add_ivar::<*mut c_void>(HOVER_CALLBACKS_IVAR);
+        unsafe {
+            decl.add_method(
+                sel!(mouseEntered:),
+                hover_mouse_entered as extern "C" fn(&Object, Sel, id),
+            );
+            decl.add_method(
+                sel!(mouseExited:),
+                hover_mouse_exited as extern "C" fn(&Object, Sel, id),
+            );
+        }
+        decl.register();
+    });
+    Class::get(HOVER_TARGET_CLASS).unwrap()
+}
 
-            Ok(view_id)
+extern "C" fn hover_mouse_entered(this: &Object, _cmd: Sel, _event: id) {
+    unsafe {
+        let callbacks: *mut c_void = *this.get_ivar(HOVER_CALLBACKS_IVAR);
+        if let Some(callbacks) = (callbacks as *const HoverCallbacks).as_ref() {
+            (callbacks.on_enter)();
         }
     }
+}
 
-    /// Create an NSGlassEffectView if available
-    unsafe fn create_glass_view(&self, bounds: NSRect) -> Result<Option<id>> {
-        if let Some(glass_class) = Class::get("NSGlassEffectView") {
-            let instance: id = msg_send![glass_class, alloc];
-            let instance: id = msg_send![instance, initWithFrame: bounds];
-            
-            if !instance.is_null() {
-                // Enable autoresizing (NSViewWidthSizable | NSViewHeightSizable)
-                let mask: usize = 2 | 16;
-                let _: () = msg_send![instance, setAutoresizingMask: mask];
-                return Ok(Some(instance));
-            }
+extern "C" fn hover_mouse_exited(this: &Object, _cmd: Sel, _event: id) {
+    unsafe {
+        let callbacks: *mut c_void = *this.get_ivar(HOVER_CALLBACKS_IVAR);
+        if let Some(callbacks) = (callbacks as *const HoverCallbacks).as_ref() {
+            (callbacks.on_exit)();
         }
-        Ok(None)
     }
+}
 
-    /// Create fallback NSVisualEffectView
-    unsafe fn create_fallback_view(&self, bounds: NSRect) -> Result<id> {
-        let visual = unsafe { NSVisualEffectView::alloc(nil) };
-        let visual: id = msg_send![visual, initWithFrame: bounds];
-        
-        if visual.is_null() {
-            return Err(GlassError::CreationFailed);
+const APPEARANCE_OBSERVER_CLASS: &str = "LiquidGlassAppearanceObserver";
+const APPEARANCE_CALLBACK_IVAR: &str = "_callback";
+
+/// Get (registering on first use) the class that receives `effectiveAppearance` KVO
+fn appearance_observer_class() -> &'static Class {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        let mut decl = ClassDecl::new(APPEARANCE_OBSERVER_CLASS, class!(NSObject))
+            .expect("appearance observer class already registered");
+        decl.add_ivar::<*mut c_void>(APPEARANCE_CALLBACK_IVAR);
+        unsafe {
+            decl.add_method(
+                sel!(observeValueForKeyPath:ofObject:change:context:),
+                appearance_did_change as extern "C" fn(&Object, Sel, id, id, id, *mut c_void),
+            );
         }
+        decl.register();
+    });
+    Class::get(APPEARANCE_OBSERVER_CLASS).unwrap()
+}
 
-        // Configure visual effect view
-        // blendingMode = 0 (behindWindow)
-        let _: () = msg_send![visual, setBlendingMode: 0_isize];
-        // material = 0 (underWindowBackground)  
-        let _: () = msg_send![visual, setMaterial: 0_isize];
-        // state = 1 (active)
-        let _: () = msg_send![visual, setState: 1_isize];
-        
-        // Enable autoresizing
-        let mask: usize = 2 | 16;
-        let _: () = msg_send![visual, setAutoresizingMask: mask];
+extern "C" fn appearance_did_change(
+    this: &Object,
+    _cmd: Sel,
+    _key_path: id,
+    object: id,
+    _change: id,
+    _context: *mut c_void,
+) {
+    unsafe {
+        let callback: *mut c_void = *this.get_ivar(APPEARANCE_CALLBACK_IVAR);
+        if let Some(callback) = (callback as *const Box<dyn Fn(Appearance)>).as_ref() {
+            callback(effective_appearance(object));
+        }
+    }
+}
 
-        Ok(visual)
+/// Bookkeeping for a single managed view
+pub struct ViewRecord {
+    /// The glass (or fallback) view
+    pub view: id,
+    /// The opaque background view, if one was requested
+    pub background: Option<id>,
+    /// Backend the view was created with
+    pub backend: GlassBackend,
+    /// Options the view was configured with
+    pub options: GlassOptions,
+    /// Caller-provided data associated with the view
+    pub user_data: i64,
+    /// Hover tracking installed by `set_hover_callbacks`
+    pub hover: Option<HoverTracking>,
+    /// Image view installed beneath the glass by `set_background_image`
+    pub background_image: Option<id>,
+    /// Blending mode explicitly chosen for the view
+    pub blending_mode: Option<BlendingMode>,
+    /// Backend to restore once the accessibility policy no longer applies
+    pub policy_backend: Option<GlassBackend>,
+    /// Last material variant set
+    pub variant: Option<GlassMaterialVariant>,
+    /// Last scrim state set
+    pub scrim_state: Option<i64>,
+    /// Last subdued state set
+    pub subdued_state: Option<i64>,
+    /// Backend to restore on `resume_all`
+    pub suspended_backend: Option<GlassBackend>,
+    /// Window and original content view, when the glass replaced it
+    pub replaced_content: Option<(id, id)>,
+    /// Handle the view was added to, if it is attached
+    pub container: Option<id>,
+    /// View casting the shadow beneath a rounded glass view
+    pub shadow_view: Option<id>,
+    /// Window key/resign-key observers installed by `set_key_state_callback`
+    pub key_observers: Vec<NotificationObserver>,
+    /// Window live-resize observers installed by `set_live_resize_callbacks`
+    pub live_resize_observers: Vec<NotificationObserver>,
+    /// Window resize observer installed by `auto_track_window`
+    pub resize_observer: Option<NotificationObserver>,
+    /// Clip view frame observer installed by `add_glass_to_scroll_view`
+    pub clip_observer: Option<NotificationObserver>,
+    /// Clip view followed by `add_glass_to_scroll_view`
+    pub clip_view: Option<id>,
+    /// `drawsBackground` of the scroll view and clip view before the glass
+    /// was added, restored on removal
+    pub scroll_draws_background: Option<(bool, bool)>,
+    /// Static image shown in place of the live view while frozen
+    pub snapshot: Option<id>,
+    /// Base tint and contrast set by `set_adaptive_tint`
+    pub adaptive_tint: Option<(String, f64)>,
+    /// Alpha to restore while `set_enabled_appearance` has the view disabled
+    pub disabled_alpha: Option<f64>,
+    /// Last emphasized state set with `set_emphasized`
+    pub emphasized: Option<bool>,
+    /// Last thickness set with `set_thickness`
+    pub thickness: Option<f64>,
+    /// Observer installed by `set_effective_appearance_callback`
+    pub appearance_observer: Option<AppearanceObserver>,
+    /// Glass container merging this view with the other segments of its group
+    pub group: Option<id>,
+    /// Content embedded by `set_glass_content`
+    pub content: Option<id>,
+    /// Whether this is a separator from `add_separator` rather than glass
+    pub separator: bool,
+    /// Whether observer callbacks run, shared with the observers' closures
+    pub observers_enabled: Rc<Cell<bool>>,
+}
+
+impl ViewRecord {
+    fn new(view: id, background: Option<id>, backend: GlassBackend, options: GlassOptions) -> Self {
+        Self {
+            view,
+            background,
+            backend,
+            options,
+            user_data: 0,
+            hover: None,
+            background_image: None,
+            blending_mode: None,
+            policy_backend: None,
+            variant: None,
+            scrim_state: None,
+            subdued_state: None,
+            suspended_backend: None,
+            replaced_content: None,
+            container: None,
+            shadow_view: None,
+            key_observers: Vec::new(),
+            live_resize_observers: Vec::new(),
+            resize_observer: None,
+            clip_observer: None,
+            clip_view: None,
+            scroll_draws_background: None,
+            snapshot: None,
+            adaptive_tint: None,
+            disabled_alpha: None,
+            emphasized: None,
+            thickness: None,
+            appearance_observer: None,
+            group: None,
+            content: None,
+            separator: false,
+            observers_enabled: Rc::new(Cell::new(true)),
+        }
     }
 
-    /// Create opaque background view
-    unsafe fn create_background_view(&self, bounds: NSRect) -> Result<id> {
-        let box_class = Class::get("NSBox").ok_or(GlassError::CreationFailed)?;
-        let bg: id = msg_send![box_class, alloc];
-        let bg: id = msg_send![bg, initWithFrame: bounds];
-        
-        if bg.is_null() {
-            return Err(GlassError::CreationFailed);
+    /// Borrowed summary passed to callers' closures
+    fn info(&self) -> ViewInfo<'_> {
+        ViewInfo {
+            backend: self.backend,
+            options: &self.options,
+            variant: self.variant,
+            user_data: self.user_data,
+            container: self.container.unwrap_or(nil) as *mut c_void,
         }
+    }
 
-        // Configure box
-        let _: () = msg_send![bg, setBoxType: 4_isize]; // NSBoxCustom
-        let _: () = msg_send![bg, setBorderType: 0_isize]; // NSNoBorder
-        
-        // Set background color
-        let window_bg_class = Class::get("NSColor").ok_or(GlassError::CreationFailed)?;
-        let window_bg_color: id = msg_send![window_bg_class, windowBackgroundColor];
-        let _: () = msg_send![bg, setFillColor: window_bg_color];
-        
-        // Enable layer and autoresizing
-        let _: () = msg_send![bg, setWantsLayer: YES];
-        let mask: usize = 2 | 16;
-        let _: () = msg_send![bg, setAutoresizingMask: mask];
+    /// Estimated number of view-sized surfaces and their size in bytes
+    ///
+    /// Counts the glass itself plus its background, shadow, background image,
+    /// snapshot, tint layers and grain, each as a full-size bitmap.
+    unsafe fn footprint(&self) -> (usize, u64) {
+        let layers = 1
+            + self.background.is_some() as usize
+            + self.shadow_view.is_some() as usize
+            + self.background_image.is_some() as usize
+            + self.snapshot.is_some() as usize
+            + self.options.tint_layers.len()
+            + self.options.grain_intensity.is_some_and(|intensity| intensity > 0.0) as usize;
 
-        Ok(bg)
+        let bounds: NSRect = msg_send![self.view, bounds];
+        let scale = unsafe { backing_scale(self.view) };
+        let pixels = (bounds.size.width * scale).max(0.0).ceil() as u64
+            * (bounds.size.height * scale).max(0.0).ceil() as u64;
+
+        (layers, pixels * BYTES_PER_PIXEL * layers as u64)
     }
 
-    /// Add subview with positioning
-    unsafe fn add_subview(
-        &self,
-        container: id,
-        subview: id,
-        relative_to: id,
-    ) -> Result<()> {
-        let positioned = -1_isize; // NSWindowBelow
-        
-        let _: () = msg_send![
-            container,
-            addSubview: subview
-            positioned: positioned
-            relativeTo: relative_to
-        ];
-        
-        Ok(())
+    /// Human-readable name for logging, e.g. `3 ("sidebar")`
+    pub fn label(&self, view_id: i32) -> String {
+        match self.options.identifier {
+            Some(ref identifier) => format!("{} ({:?})", view_id, identifier),
+            None => view_id.to_string(),
+        }
     }
+}
 
-    /// Configure glass view with options
-    unsafe fn configure_glass_view(&self, view: id, options: &GlassOptions) -> Result<()> {
-        // Set corner radius
-        if options.corner_radius > 0.0 {
-            let _: () = msg_send![view, setWantsLayer: YES];
-            let layer: id = msg_send![view, layer];
-            if !layer.is_null() {
-                let _: () = msg_send![layer, setCornerRadius: options.corner_radius];
-                let _: () = msg_send![layer, setMasksToBounds: YES];
+/// Manager for macOS glass effects
+pub struct MacOSGlassManager {
+    views: HashMap<i32, ViewRecord>,
+    next_id: i32,
+    free_ids: BTreeSet<i32>,
+    recycle_ids: bool,
+    dedup_policy: DedupPolicy,
+    assume_supported: bool,
+    pending_bounds_sync: HashSet<i32>,
+    accessibility_policy: AccessibilityPolicy,
+    accessibility_observer: Option<NotificationObserver>,
+    window_move_observer: Option<NotificationObserver>,
+    debug_overlay: bool,
+    env_defaults: Option<EnvDefaults>,
+    glass_class: Option<&'static Class>,
+}
+
+impl MacOSGlassManager {
+    /// Create a new macOS glass manager
+    pub fn new() -> Self {
+        Self {
+            views: HashMap::new(),
+            next_id: 0,
+            free_ids: BTreeSet::new(),
+            recycle_ids: false,
+            dedup_policy: DedupPolicy::default(),
+            assume_supported: false,
+            pending_bounds_sync: HashSet::new(),
+            accessibility_policy: AccessibilityPolicy::default(),
+            accessibility_observer: None,
+            window_move_observer: None,
+            debug_overlay: false,
+            env_defaults: None,
+            glass_class: None,
+        }
+    }
+
+    /// Report support without probing the runtime
+    pub fn set_assume_supported(&mut self, assume_supported: bool) {
+        self.assume_supported = assume_supported;
+    }
+
+    /// Check if glass effects are supported on this macOS version
+    pub fn is_supported(&self) -> bool {
+        if self.assume_supported {
+            return true;
+        }
+
+        // NSGlassEffectView is available on macOS 15+. Deliberately not cached
+        // so the answer always reflects the currently loaded AppKit.
+        Class::get("NSGlassEffectView").is_some()
+    }
+
+    /// Switch every view to the solid color backend, remembering its backend
+    ///
+    /// Separators and window content views are skipped. A view that fails to
+    /// switch does not stop the others; the first error is returned at the end.
+    pub fn suspend_all(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        let view_ids: Vec<i32> = self.views.keys().copied().collect();
+        for view_id in view_ids {
+            let record = &self.views[&view_id];
+            if record.suspended_backend.is_some()
+                || record.backend == GlassBackend::SolidColor
+                || record.separator
+                || record.replaced_content.is_some()
+            {
+                continue;
+            }
+
+            let previous = record.backend;
+            match self.set_backend(view_id, GlassBackend::SolidColor) {
+                Ok(()) => self.views.get_mut(&view_id).unwrap().suspended_backend = Some(previous),
+                Err(err) => {
+                    log::warn!("Failed to suspend view {}: {}", self.views[&view_id].label(view_id), err);
+                    result = result.and(Err(err));
+                }
             }
         }
+        result
+    }
 
-        // Set tint color
-        if let Some(ref tint) = options.tint_color {
-            if let Ok(color) = unsafe { self.parse_hex_color(tint) } {
-                // Try to set tintColor using runtime
-                let sel = sel!(setTintColor:);
-                let responds: bool = msg_send![view, respondsToSelector: sel];
-                if responds {
-                    let _: () = msg_send![view, setTintColor: color];
-                } else {
-                    let layer: id = msg_send![view, layer];
-                    if !layer.is_null() {
-                        // Fallback to layer backgroundColor
-                        let cg_color: id = msg_send![color, CGColor];
-                        let _: () = msg_send![layer, setBackgroundColor: cg_color];
+    /// Restore every view suspended by `suspend_all` to its previous backend
+    ///
+    /// A view that fails to switch back stays suspended and does not stop the
+    /// others; the first error is returned at the end.
+    pub fn resume_all(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        let view_ids: Vec<i32> = self.views.keys().copied().collect();
+        for view_id in view_ids {
+            if let Some(previous) = self.views[&view_id].suspended_backend {
+                match self.set_backend(view_id, previous) {
+                    Ok(()) => self.views.get_mut(&view_id).unwrap().suspended_backend = None,
+                    Err(err) => {
+                        log::warn!("Failed to resume view {}: {}", self.views[&view_id].label(view_id), err);
+                        result = result.and(Err(err));
                     }
                 }
             }
         }
+        result
+    }
 
-        Ok(())
+    /// Check the "Reduce Transparency" accessibility setting
+    pub fn reduce_transparency_enabled(&self) -> bool {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let enabled: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceTransparency];
+            enabled == YES
+        }
     }
 
-    /// Parse hex color string to NSColor
-    unsafe fn parse_hex_color(&self, hex: &str) -> Result<id> {
-        let cleaned = hex.trim().trim_start_matches('#');
-        
-        if cleaned.len() != 6 && cleaned.len() != 8 {
-            return Err(GlassError::InvalidColor(hex.to_string()));
+    /// Check the "Reduce Motion" accessibility setting
+    pub fn reduce_motion_enabled(&self) -> bool {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let enabled: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceMotion];
+            enabled == YES
         }
+    }
 
-        let rgba = u32::from_str_radix(cleaned, 16)
-            .map_err(|_| GlassError::InvalidColor(hex.to_string()))?;
+    /// Use the solid color backend for new views while "Reduce Transparency" is on
+    pub fn set_honor_reduce_transparency(&mut self, honor: bool) {
+        self.accessibility_policy.honor_reduce_transparency = honor;
+    }
 
-        let (r, g, b, a) = if cleaned.len() == 6 {
-            (
-                ((rgba >> 16) & 0xFF) as f64 / 255.0,
-                ((rgba >> 8) & 0xFF) as f64 / 255.0,
-                (rgba & 0xFF) as f64 / 255.0,
-                1.0,
-            )
-        } else {
-            (
-                ((rgba >> 24) & 0xFF) as f64 / 255.0,
-                ((rgba >> 16) & 0xFF) as f64 / 255.0,
-                ((rgba >> 8) & 0xFF) as f64 / 255.0,
-                (rgba & 0xFF) as f64 / 255.0,
-            )
-        };
+    /// Set how accessibility display settings are honored
+    pub fn set_accessibility_policy(&mut self, policy: AccessibilityPolicy) {
+        self.accessibility_policy = policy;
+    }
 
-        let color = unsafe { NSColor::colorWithSRGBRed_green_blue_alpha_(nil, r, g, b, a) };
-        Ok(color)
+    /// Install the observer that keeps live views in line with the policy
+    pub fn ensure_accessibility_observer(&mut self, make: impl FnOnce() -> NotificationObserver) {
+        if self.accessibility_observer.is_none() {
+            self.accessibility_observer = Some(make());
+        }
     }
 
-    /// Set glass material variant
-    pub fn set_variant(&self, view_id: i32, variant: GlassMaterialVariant) -> Result<()> {
-        self.set_int_property(view_id, "variant", variant as i64)
+    /// Keep the observer that reapplies options after window moves
+    pub fn set_window_move_observer(&mut self, observer: NotificationObserver) {
+        self.window_move_observer = Some(observer);
     }
 
-    /// Set integer property using runtime
-    pub fn set_int_property(&self, view_id: i32, key: &str, value: i64) -> Result<()> {
-        let view = self.views.get(&view_id)
+    /// Reapply options to the managed view backed by `view`, if any
+    pub fn reapply_native_view(&mut self, view: id) {
+        let view_id = self.views.iter().find_map(|(&view_id, record)| {
+            (record.view == view).then_some(view_id)
+        });
+        if let Some(view_id) = view_id
+            && let Err(err) = self.reapply(view_id)
+        {
+            log::warn!(
+                "Failed to reapply view {} after window change: {}",
+                self.views[&view_id].label(view_id),
+                err
+            );
+        }
+    }
+
+    /// Reapply a view's options, variant and states to its native view
+    pub fn reapply(&mut self, view_id: i32) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get(&view_id)
             .ok_or(GlassError::InvalidViewId(view_id))?;
 
+        // A view made to look disabled keeps its greyed tint and subdued state
+        let disabled = record.disabled_alpha.is_some();
+        let disabled_options = disabled.then(|| disabled_tint_options(&record.options)).flatten();
         unsafe {
-            // Try private setter first (set_key:)
-            let private_setter = format!("set_{}:", key);
-            if let Some(sel) = self.try_get_selector(&private_setter) {
-                let responds: bool = msg_send![*view, respondsToSelector: sel];
-                if responds {
-                    // Use NSInvocation or performSelector for setting int values
-                    let number: id = msg_send![class!(NSNumber), numberWithLongLong: value];
-                    let _: () = msg_send![*view, setValue:number forKey: NSString::alloc(nil).init_str(key)];
-                    return Ok(());
+            self.configure_glass_view(record.view, &record.options)?;
+            let tint_options = disabled_options.as_ref().unwrap_or(&record.options);
+            self.apply_tint(record.view, tint_options, tint_options.tint_timing);
+            if let Some(mode) = record.blending_mode {
+                apply_blending_mode(record.view, mode);
+            }
+        }
+
+        let (variant, scrim_state, subdued_state) =
+            (record.variant, record.scrim_state, record.subdued_state);
+        if let Some(variant) = variant {
+            self.set_variant(view_id, variant)?;
+        }
+        if let Some(state) = scrim_state {
+            self.set_scrim_state(view_id, state)?;
+        }
+        if disabled {
+            // Not every backend has a subdued state
+            let _ = self.set_int_property(view_id, "subduedState", 1);
+        } else if let Some(state) = subdued_state {
+            self.set_subdued_state(view_id, state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply new options to a live view in place, keeping its ID and native view
+    ///
+    /// The opaque background is added or removed as needed, and a changed
+    /// `backend` goes through `set_backend`. Placement options (`frame`, `pin`,
+    /// `aspect_ratio`) are left as they are.
+    pub fn update_glass_view(&mut self, view_id: i32, options: GlassOptions) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        let options = resolve_options(options);
+        let view = record.view;
+        let (old_background, old_shadow_view) = (record.background, record.shadow_view);
+
+        let tint_changed = options.tint_color != record.options.tint_color
+            || options.fallback_tint_color != record.options.fallback_tint_color;
+        let identifier_cleared = options.identifier.is_none() && record.options.identifier.is_some();
+        let wants_layer_cleared = options.wants_layer.is_none() && record.options.wants_layer.is_some();
+
+        let (background, shadow_view) = unsafe {
+            // Settings the new options leave unset would otherwise stay behind
+            if tint_changed {
+                clear_tint(view);
+            }
+            if identifier_cleared {
+                let _: () = msg_send![view, setIdentifier: nil];
+            }
+            // Options that need a layer turn it back on while reconfiguring
+            if wants_layer_cleared
+                && let Some(wants_layer) = stored_flag(view, &DEFAULT_WANTS_LAYER_KEY)
+            {
+                let _: () = msg_send![view, setWantsLayer: if wants_layer { YES } else { NO }];
+            }
+            if options.rim_highlight.is_none()
+                && let Some(rim) = rim_layer(view)
+            {
+                let _: () = msg_send![rim, removeFromSuperlayer];
+            }
+            if options.shadow.is_none() || options.corner_radius > 0.0 {
+                let layer: id = msg_send![view, layer];
+                if !layer.is_null() {
+                    let _: () = msg_send![layer, setShadowOpacity: 0.0_f32];
                 }
             }
 
-            // Try public setter (setKey:)
-            let public_setter = format!(
-                "set{}{}:",
-                key.chars().next().unwrap().to_uppercase(),
-                &key[1..]
-            );
-            if let Some(sel) = self.try_get_selector(&public_setter) {
-                let responds: bool = msg_send![*view, respondsToSelector: sel];
-                if responds {
-                    let number: id = msg_send![class!(NSNumber), numberWithLongLong: value];
-                    let _: () = msg_send![*view, setValue:number forKey: NSString::alloc(nil).init_str(key)];
-                    return Ok(());
+            self.configure_glass_view(view, &options)?;
+            self.apply_tint(view, &options, options.tint_timing);
+
+            let background = match old_background {
+                Some(bg) if !options.opaque => {
+                    let _: () = msg_send![bg, removeFromSuperview];
+                    None
+                }
+                None if options.opaque => {
+                    let container: id = msg_send![view, superview];
+                    let frame: NSRect = msg_send![view, frame];
+                    let bg = self.create_background_view(frame)?;
+                    let mask: usize = msg_send![view, autoresizingMask];
+                    let _: () = msg_send![bg, setAutoresizingMask: mask];
+                    if !container.is_null() {
+                        let positioned = -1_isize; // NSWindowBelow
+                        let _: () = msg_send![
+                            container,
+                            addSubview: bg
+                            positioned: positioned
+                            relativeTo: view
+                        ];
+                        if let Some(pin) = options.pin {
+                            pin_to_container(bg, container, pin);
+                        }
+                    }
+                    Some(bg)
                 }
+                background => background,
+            };
+            if let Some(bg) = background {
+                round_background(bg, &options);
             }
 
-            Err(GlassError::RuntimeError(format!(
-                "Property '{}' not found or not accessible",
-                key
-            )))
+            let shadow_view = self.attach_shadow_view(view, old_shadow_view, &options)?;
+            if let Some(old) = old_shadow_view
+                && shadow_view != Some(old)
+            {
+                let _: () = msg_send![old, removeFromSuperview];
+            }
+
+            (background, shadow_view)
+        };
+
+        let record = self.views.get_mut(&view_id).unwrap();
+        let radius = options.corner_radius;
+        let backend = options.backend;
+        record.options = options;
+        record.background = background;
+        record.shadow_view = shadow_view;
+
+        // Also squares off corners when the radius drops to 0
+        self.set_corner_radius(view_id, radius)?;
+        self.refresh_debug_overlay(view_id);
+
+        // A suspended view, or one held on the solid color backend by the
+        // accessibility policy, switches once that ends
+        if let Some(backend) = backend {
+            let record = self.views.get_mut(&view_id).unwrap();
+            if record.suspended_backend.is_some() {
+                record.suspended_backend = Some(backend);
+            } else if record.policy_backend.is_some() {
+                record.policy_backend = Some(backend);
+            } else {
+                self.set_backend(view_id, backend)?;
+            }
         }
+        Ok(())
     }
 
-    /// Try to get a selector
-    fn try_get_selector(&self, name: &str) -> Option<Sel> {
-        Some(Sel::register(name))
+    /// Whether animated changes should run (false while honoring Reduce Motion)
+    pub fn animations_allowed(&self) -> bool {
+        !(self.accessibility_policy.honor_reduce_motion && self.reduce_motion_enabled())
     }
 
-    /// Remove a glass view
-    pub fn remove_view(&mut self, view_id: i32) -> Result<()> {
-        let view = self.views.remove(&view_id)
+    /// Backend a new view gets when the accessibility policy does not apply
+    fn glass_backend(&self) -> GlassBackend {
+        if self.glass_class().is_some() {
+            GlassBackend::Native
+        } else {
+            GlassBackend::Fallback
+        }
+    }
+
+    /// A record for a view from `create_views`
+    ///
+    /// Views created as solid color because of the accessibility policy
+    /// remember the backend to restore once the policy no longer applies.
+    fn new_record(
+        &self,
+        view: id,
+        background: Option<id>,
+        backend: GlassBackend,
+        options: GlassOptions,
+    ) -> ViewRecord {
+        let requested = options.backend;
+        let mut record = ViewRecord::new(view, background, backend, options);
+        if backend == GlassBackend::SolidColor && requested != Some(GlassBackend::SolidColor) {
+            record.policy_backend = Some(requested.unwrap_or_else(|| self.glass_backend()));
+        }
+        record
+    }
+
+    /// Whether views should currently use the solid color backend
+    fn use_solid_color(&self) -> bool {
+        self.accessibility_policy.honor_reduce_transparency && self.reduce_transparency_enabled()
+    }
+
+    /// Move live views to or from the solid color backend to match the policy
+    pub fn apply_accessibility_policy(&mut self) {
+        let solid = self.use_solid_color();
+        let view_ids: Vec<i32> = self.views.keys().copied().collect();
+
+        for view_id in view_ids {
+            let record = &self.views[&view_id];
+            if record.separator {
+                continue;
+            }
+            if solid && record.backend != GlassBackend::SolidColor {
+                let previous = record.backend;
+                if self.set_backend(view_id, GlassBackend::SolidColor).is_ok() {
+                    self.views.get_mut(&view_id).unwrap().policy_backend = Some(previous);
+                }
+            } else if !solid
+                && let Some(previous) = record.policy_backend
+                && self.set_backend(view_id, previous).is_ok()
+            {
+                self.views.get_mut(&view_id).unwrap().policy_backend = None;
+            }
+        }
+    }
+
+    /// Add a glass effect view to a window
+    pub fn add_glass_view(
+        &mut self,
+        window_handle: *mut c_void,
+        options: GlassOptions,
+    ) -> Result<i32> {
+        self.add_glass_view_at(window_handle, options, AttachPosition::Bottom)
+    }
+
+    /// Add a glass effect view at a chosen position among the container's subviews
+    pub fn add_glass_view_at(
+        &mut self,
+        window_handle: *mut c_void,
+        options: GlassOptions,
+        position: AttachPosition,
+    ) -> Result<i32> {
+        self.insert_glass_view(window_handle, options, position, true)
+    }
+
+    /// `add_glass_view_at`, optionally without applying the dedup policy
+    ///
+    /// Group segments skip it, since the group already applied it once and
+    /// each segment would otherwise see the previous one.
+    fn insert_glass_view(
+        &mut self,
+        window_handle: *mut c_void,
+        options: GlassOptions,
+        position: AttachPosition,
+        dedup: bool,
+    ) -> Result<i32> {
+        let options = self.resolve_new_options(options);
+
+        unsafe {
+            self.ensure_main_thread()?;
+            self.check_id_available()?;
+
+            // Cast the window handle to NSView
+            let root_view = window_handle as id;
+            if root_view.is_null() {
+                return Err(GlassError::InvalidHandle);
+            }
+
+            if dedup {
+                self.apply_dedup_policy(root_view)?;
+            }
+
+            // Get bounds, or the explicitly requested frame
+            let bounds: NSRect = match options.frame {
+                Some(frame) => frame.into(),
+                None => msg_send![root_view, bounds],
+            };
+
+            let (glass_view, background_view, backend) = self.create_views(bounds, &options)?;
+
+            self.apply_tint(glass_view, &options, TintTiming::BeforeAttach);
+
+            // Add views to container
+            self.attach_views(
+                root_view,
+                glass_view,
+                background_view,
+                position,
+                options.plain_add_subview,
+            )?;
+
+            // Configure the glass view
+            self.configure_glass_view(glass_view, &options)?;
+            self.apply_tint(glass_view, &options, TintTiming::AfterAttach);
+            let shadow_view = self.attach_shadow_view(glass_view, None, &options)?;
+            if let Some(pin) = options.pin {
+                for view in [Some(glass_view), background_view, shadow_view].into_iter().flatten() {
+                    pin_to_container(view, root_view, pin);
+                    if let Some(ratio) = options.aspect_ratio {
+                        constrain_aspect_ratio(view, ratio);
+                    }
+                }
+            }
+
+            // Store view ID
+            let view_id = self.allocate_id()?;
+            
+            let mut record = self.new_record(glass_view, background_view, backend, options);
+            record.container = Some(root_view);
+            record.shadow_view = shadow_view;
+            self.views.insert(view_id, record);
+            self.refresh_debug_overlay(view_id);
+            self.apply_env_variant(view_id);
+
+            Ok(view_id)
+        }
+    }
+
+    /// Add a glass view behind a window's titlebar contents
+    ///
+    /// The glass goes at the bottom of the titlebar view inside
+    /// `NSTitlebarContainerView`, below the window buttons and any titlebar
+    /// accessory views.
+    pub fn add_glass_to_titlebar(
+        &mut self,
+        window_ptr: *mut c_void,
+        options: GlassOptions,
+    ) -> Result<i32> {
+        let titlebar = unsafe {
+            self.ensure_main_thread()?;
+
+            let window = window_ptr as id;
+            if window.is_null() {
+                return Err(GlassError::InvalidHandle);
+            }
+
+            // The close button lives directly in the titlebar view
+            let close_button: id = msg_send![window, standardWindowButton: 0_usize];
+            let titlebar: id = if close_button.is_null() {
+                nil
+            } else {
+                msg_send![close_button, superview]
+            };
+            if titlebar.is_null() {
+                return Err(GlassError::InvalidState(
+                    "window has no titlebar".to_string(),
+                ));
+            }
+            titlebar
+        };
+
+        self.add_glass_view(titlebar as *mut c_void, options)
+    }
+
+    /// Add a glass view behind a scroll view's clip view, tracking its frame
+    ///
+    /// `on_clip_resize` is called with the new view's ID whenever the clip
+    /// view's frame changes, and should call `sync_clip_frame`.
+    pub fn add_glass_to_scroll_view(
+        &mut self,
+        scroll_view_ptr: *mut c_void,
+        options: GlassOptions,
+        on_clip_resize: Box<dyn Fn(i32)>,
+    ) -> Result<i32> {
+        let (scroll_view, clip_view, frame, draws_background) = unsafe {
+            self.ensure_main_thread()?;
+
+            let scroll_view = scroll_view_ptr as id;
+            let is_scroll_view: bool = !scroll_view.is_null()
+                && msg_send![scroll_view, isKindOfClass: class!(NSScrollView)];
+            if !is_scroll_view {
+                return Err(GlassError::InvalidHandle);
+            }
+
+            // Let the glass show through the scroll view's own backgrounds
+            let clip_view: id = msg_send![scroll_view, contentView];
+            let scroll_draws: BOOL = msg_send![scroll_view, drawsBackground];
+            let clip_draws: BOOL = msg_send![clip_view, drawsBackground];
+            let _: () = msg_send![scroll_view, setDrawsBackground: NO];
+            let _: () = msg_send![clip_view, setDrawsBackground: NO];
+
+            let frame: NSRect = msg_send![clip_view, frame];
+            (scroll_view, clip_view, frame, (scroll_draws == YES, clip_draws == YES))
+        };
+
+        // A sibling below the clip view does not scroll with the document
+        let options = GlassOptions {
+            frame: Some(Rect::new(frame.origin.x, frame.origin.y, frame.size.width, frame.size.height)),
+            pin: None,
+            ..options
+        };
+        let view_id = match self.add_glass_view_at(
+            scroll_view as *mut c_void,
+            options,
+            AttachPosition::Below(clip_view as *mut c_void),
+        ) {
+            Ok(view_id) => view_id,
+            Err(err) => {
+                unsafe { restore_draws_background(scroll_view, clip_view, draws_background) };
+                return Err(err);
+            }
+        };
+
+        // Follow the clip view as scrollers, rulers and insets resize it
+        let record = self.views.get_mut(&view_id).unwrap();
+        record.clip_view = Some(clip_view);
+        record.scroll_draws_background = Some(draws_background);
+        record.clip_observer = Some(unsafe {
+            let _: () = msg_send![clip_view, setPostsFrameChangedNotifications: YES];
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            NotificationObserver::new(center, "NSViewFrameDidChangeNotification", clip_view, move || {
+                on_clip_resize(view_id)
+            })
+        });
+
+        Ok(view_id)
+    }
+
+    /// Match a scroll view's glass to its clip view's current frame
+    pub fn sync_clip_frame(&self, view_id: i32) -> Result<()> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        let Some(clip_view) = record.clip_view else {
+            return Ok(());
+        };
+
+        unsafe {
+            let frame: NSRect = msg_send![clip_view, frame];
+            for view in [
+                Some(record.view),
+                record.background,
+                record.background_image,
+                record.shadow_view,
+                record.snapshot,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let _: () = msg_send![view, setFrame: frame];
+            }
+            if let Some(shadow_view) = record.shadow_view {
+                update_shadow_path(shadow_view, record.options.corner_radius);
+            }
+            layout_rim_highlight(record.view);
+            layout_overlay_layers(record.view);
+        }
+
+        Ok(())
+    }
+
+    /// Make a window transparent so glass in it blurs what is behind the window
+    pub fn prepare_window_for_glass(&self, window_ptr: *mut c_void) -> Result<()> {
+        unsafe {
+            self.ensure_main_thread()?;
+
+            let window = window_ptr as id;
+            if window.is_null() {
+                return Err(GlassError::InvalidHandle);
+            }
+
+            let clear: id = msg_send![class!(NSColor), clearColor];
+            let _: () = msg_send![window, setOpaque: NO];
+            let _: () = msg_send![window, setBackgroundColor: clear];
+            // The shadow follows the window's opaque pixels, so recompute it
+            let _: () = msg_send![window, setHasShadow: YES];
+            let _: () = msg_send![window, invalidateShadow];
+        }
+
+        Ok(())
+    }
+
+    /// Make a glass view the window's content view, moving the existing content into it
+    pub fn set_as_content_view(
+        &mut self,
+        window_ptr: *mut c_void,
+        options: GlassOptions,
+    ) -> Result<i32> {
+        let options = self.resolve_new_options(options);
+
+        unsafe {
+            self.ensure_main_thread()?;
+            self.check_id_available()?;
+
+            let window = window_ptr as id;
+            if window.is_null() {
+                return Err(GlassError::InvalidHandle);
+            }
+
+            let content: id = msg_send![window, contentView];
+            let bounds: NSRect = if content.is_null() {
+                let frame: NSRect = msg_send![window, frame];
+                NSRect::new(NSPoint::new(0.0, 0.0), frame.size)
+            } else {
+                msg_send![content, bounds]
+            };
+
+            let (glass_view, backend) = match options.backend {
+                Some(backend) => (self.create_view_for_backend(backend, bounds, &options)?, backend),
+                None => match self.create_glass_view(bounds)? {
+                    Some(glass_view) => (glass_view, GlassBackend::Native),
+                    None => (self.create_fallback_view(bounds)?, GlassBackend::Fallback),
+                },
+            };
+
+            // Keep the old content alive while it moves under the glass
+            if !content.is_null() {
+                let _: id = msg_send![content, retain];
+            }
+            self.apply_tint(glass_view, &options, TintTiming::BeforeAttach);
+            let _: () = msg_send![window, setContentView: glass_view];
+            if !content.is_null() {
+                embed_content(glass_view, content);
+            }
+
+            self.configure_glass_view(glass_view, &options)?;
+            self.apply_tint(glass_view, &options, TintTiming::AfterAttach);
+
+            let view_id = self.allocate_id()?;
+            let mut record = ViewRecord::new(glass_view, None, backend, options);
+            record.container = Some(window);
+            if !content.is_null() {
+                record.replaced_content = Some((window, content));
+            }
+            self.views.insert(view_id, record);
+            self.refresh_debug_overlay(view_id);
+            self.apply_env_variant(view_id);
+
+            Ok(view_id)
+        }
+    }
+
+    /// Place a content view inside a glass view
+    pub fn set_glass_content(&mut self, view_id: i32, content: *mut c_void) -> Result<()> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        let content = content as id;
+        if content.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        unsafe {
+            self.ensure_main_thread()?;
+            if let Some(old) = record.content
+                && old != content
+            {
+                let _: () = msg_send![old, removeFromSuperview];
+            }
+            embed_content(record.view, content);
+        }
+
+        self.views.get_mut(&view_id).unwrap().content = Some(content);
+        Ok(())
+    }
+
+    /// Create and configure a glass view without adding it to a container
+    pub fn create_detached_glass_view(&mut self, bounds: Rect, options: GlassOptions) -> Result<i32> {
+        let options = self.resolve_new_options(options);
+
+        unsafe {
+            self.ensure_main_thread()?;
+            self.check_id_available()?;
+
+            let bounds: NSRect = bounds.into();
+            let (glass_view, background_view, backend) = self.create_views(bounds, &options)?;
+            self.configure_glass_view(glass_view, &options)?;
+            // Tints timed for after attaching are applied by `attach_view`
+            self.apply_tint(glass_view, &options, TintTiming::BeforeAttach);
+
+            let view_id = self.allocate_id()?;
+
+            self.views.insert(
+                view_id,
+                self.new_record(glass_view, background_view, backend, options),
+            );
+            self.refresh_debug_overlay(view_id);
+            self.apply_env_variant(view_id);
+
+            Ok(view_id)
+        }
+    }
+
+    /// Insert a previously created view into a container
+    pub fn attach_view(
+        &mut self,
+        view_id: i32,
+        container: *mut c_void,
+        position: AttachPosition,
+    ) -> Result<()> {
+        let record = self.views.get(&view_id)
             .ok_or(GlassError::InvalidViewId(view_id))?;
 
+        let container = container as id;
+        if container.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
         unsafe {
-            let _: () = msg_send![view, removeFromSuperview];
+            self.ensure_main_thread()?;
+            self.attach_views(
+                container,
+                record.view,
+                record.background,
+                position,
+                record.options.plain_add_subview,
+            )?;
+            self.apply_tint(record.view, &record.options, TintTiming::AfterAttach);
+        }
+
+        let shadow_view = unsafe {
+            self.attach_shadow_view(record.view, record.shadow_view, &record.options)?
+        };
+
+        if let Some(pin) = record.options.pin {
+            for view in [Some(record.view), record.background, shadow_view].into_iter().flatten() {
+                unsafe { pin_to_container(view, container, pin) };
+            }
         }
 
+        let record = self.views.get_mut(&view_id).unwrap();
+        record.container = Some(container);
+        record.shadow_view = shadow_view;
         Ok(())
     }
+
+    /// Add a thin separator view above a container's subviews
+    pub fn add_separator(
+        &mut self,
+        container_ptr: *mut c_void,
+        orientation: Orientation,
+        position: f64,
+    ) -> Result<i32> {
+        unsafe {
+            self.ensure_main_thread()?;
+            self.check_id_available()?;
+
+            let container = container_ptr as id;
+            if container.is_null() {
+                return Err(GlassError::InvalidHandle);
+            }
+
+            let bounds: NSRect = msg_send![container, bounds];
+            let (frame, mask) = match orientation {
+                Orientation::Horizontal => (
+                    NSRect::new(
+                        NSPoint::new(0.0, position),
+                        NSSize::new(bounds.size.width, SEPARATOR_THICKNESS),
+                    ),
+                    2_usize,
+                ),
+                Orientation::Vertical => (
+                    NSRect::new(
+                        NSPoint::new(position, 0.0),
+                        NSSize::new(SEPARATOR_THICKNESS, bounds.size.height),
+                    ),
+                    16_usize,
+                ),
+            };
+
+            let visual = NSVisualEffectView::alloc(nil);
+            let separator: id = msg_send![visual, initWithFrame: frame];
+            if separator.is_null() {
+                return Err(GlassError::CreationFailed);
+            }
+
+            // blendingMode = 1 (withinWindow), so it reads against the glass beneath
+            let _: () = msg_send![separator, setBlendingMode: 1_isize];
+            let _: () = msg_send![separator, setMaterial: SEPARATOR_MATERIAL];
+            // state = 1 (active)
+            let _: () = msg_send![separator, setState: 1_isize];
+            let _: () = msg_send![separator, setAutoresizingMask: mask];
+
+            self.attach_views(container, separator, None, AttachPosition::Top, false)?;
+
+            let view_id = self.allocate_id()?;
+            let mut record = ViewRecord::new(
+                separator,
+                None,
+                GlassBackend::Fallback,
+                GlassOptions::default(),
+            );
+            record.container = Some(container);
+            record.separator = true;
+            self.views.insert(view_id, record);
+            self.refresh_debug_overlay(view_id);
+
+            Ok(view_id)
+        }
+    }
+
+    /// Add one glass segment per frame, merged by a shared glass container
+    pub fn add_glass_group(
+        &mut self,
+        container: *mut c_void,
+        frames: Vec<Rect>,
+        options: GlassOptions,
+    ) -> Result<Vec<i32>> {
+        unsafe { self.ensure_main_thread()? };
+
+        let container = container as id;
+        if container.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+        if frames.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.apply_dedup_policy(container)?;
+
+        // NSGlassEffectContainerView merges glass views placed in its content
+        // view; without it the segments are plain siblings
+        let (group, content) = match Class::get("NSGlassEffectContainerView") {
+            Some(class) => unsafe {
+                let bounds: NSRect = msg_send![container, bounds];
+                let mask: usize = 2 | 16;
+                let group: id = msg_send![class, alloc];
+                let group: id = msg_send![group, initWithFrame: bounds];
+                let _: () = msg_send![group, setAutoresizingMask: mask];
+                let content: id = msg_send![class!(NSView), alloc];
+                let content: id = msg_send![content, initWithFrame: bounds];
+                let _: () = msg_send![content, setAutoresizingMask: mask];
+                embed_content(group, content);
+                let _: () = msg_send![content, release];
+                if let Err(err) = self.add_subview(container, group, nil) {
+                    let _: () = msg_send![group, release];
+                    return Err(err);
+                }
+                (Some(group), content)
+            },
+            None => (None, container),
+        };
+
+        let mut view_ids = Vec::with_capacity(frames.len());
+        for frame in frames {
+            let segment = GlassOptions {
+                frame: Some(frame),
+                pin: None,
+                ..options.clone()
+            };
+            match self.insert_glass_view(content as *mut c_void, segment, AttachPosition::Top, false) {
+                Ok(view_id) => view_ids.push(view_id),
+                Err(err) => {
+                    for &view_id in &view_ids {
+                        let _ = self.remove_view(view_id);
+                    }
+                    if let Some(group) = group {
+                        unsafe {
+                            let _: () = msg_send![group, removeFromSuperview];
+                            let _: () = msg_send![group, release];
+                        }
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        for &view_id in &view_ids {
+            let record = self.views.get_mut(&view_id).unwrap();
+            record.container = Some(container);
+            record.group = group;
+        }
+        Ok(view_ids)
+    }
+
+    /// Create the glass view (and opaque background, if requested) for some options
+    unsafe fn create_views(
+        &self,
+        bounds: NSRect,
+        options: &GlassOptions,
+    ) -> Result<(id, Option<id>, GlassBackend)> {
+        // Create background view if opaque
+        let background_view = if options.opaque {
+            Some(unsafe { self.create_background_view(bounds) }?)
+        } else {
+            None
+        };
+
+        if let Some(bg) = background_view {
+            unsafe { round_background(bg, options) };
+        }
+
+        let (glass_view, backend) = if self.use_solid_color() {
+            // Honor "Reduce Transparency" with an opaque view if asked to
+            (unsafe { self.create_background_view(bounds) }?, GlassBackend::SolidColor)
+        } else if let Some(backend) = options.backend {
+            (unsafe { self.create_view_for_backend(backend, bounds, options) }?, backend)
+        } else if let Some(glass_view) = unsafe { self.create_glass_view(bounds) }? {
+            // Try to create NSGlassEffectView first, fall back to NSVisualEffectView
+            (glass_view, GlassBackend::Native)
+        } else {
+            (unsafe { self.create_fallback_view(bounds) }?, GlassBackend::Fallback)
+        };
+
+        // An explicit frame should not stretch with the container
+        if options.frame.is_some() {
+            for view in background_view.into_iter().chain([glass_view]) {
+                let _: () = msg_send![view, setAutoresizingMask: 0_usize];
+            }
+        }
+
+        Ok((glass_view, background_view, backend))
+    }
+
+    /// Add a glass view and its background to a container
+    unsafe fn attach_views(
+        &self,
+        container: id,
+        glass_view: id,
+        background_view: Option<id>,
+        position: AttachPosition,
+        plain_add_subview: bool,
+    ) -> Result<()> {
+        match position {
+            AttachPosition::Bottom => {
+                if plain_add_subview {
+                    let _: () = msg_send![container, addSubview: glass_view];
+                } else {
+                    unsafe { self.add_subview(container, glass_view, nil)? };
+                }
+            }
+            AttachPosition::Top => {
+                let positioned = 1_isize; // NSWindowAbove
+                let _: () = msg_send![
+                    container,
+                    addSubview: glass_view
+                    positioned: positioned
+                    relativeTo: nil
+                ];
+            }
+            AttachPosition::Below(sibling) | AttachPosition::Above(sibling) => {
+                let positioned = match position {
+                    AttachPosition::Above(_) => 1_isize, // NSWindowAbove
+                    _ => -1_isize, // NSWindowBelow
+                };
+                let _: () = msg_send![
+                    container,
+                    addSubview: glass_view
+                    positioned: positioned
+                    relativeTo: sibling as id
+                ];
+            }
+        }
+
+        // The opaque background always sits directly beneath its glass
+        if let Some(bg) = background_view {
+            unsafe { self.add_subview(container, bg, glass_view)? };
+        }
+        Ok(())
+    }
+
+    /// Add a glass effect view and describe how it was created
+    pub fn add_glass_view_detailed(
+        &mut self,
+        window_handle: *mut c_void,
+        options: GlassOptions,
+    ) -> Result<GlassViewHandle> {
+        let view_id = self.add_glass_view(window_handle, options)?;
+        let record = &self.views[&view_id];
+
+        let native_tint: bool = unsafe {
+            msg_send![record.view, respondsToSelector: sel!(setTintColor:)]
+        };
+
+        Ok(GlassViewHandle {
+            id: view_id,
+            backend: record.backend,
+            native_tint,
+        })
+    }
+
+    /// Reuse the IDs of removed views for new views
+    pub fn set_recycle_ids(&mut self, recycle: bool) {
+        self.recycle_ids = recycle;
+        if !recycle {
+            self.free_ids.clear();
+        }
+    }
+
+    /// Use environment overrides for options left at their defaults
+    pub fn set_env_defaults(&mut self, defaults: EnvDefaults) {
+        self.env_defaults = Some(defaults);
+    }
+
+    /// Apply environment overrides, then resolve options for a new view
+    fn resolve_new_options(&self, options: GlassOptions) -> GlassOptions {
+        match self.env_defaults {
+            Some(ref env) => resolve_options(env.apply(options)),
+            None => resolve_options(options),
+        }
+    }
+
+    /// Set the `GLASS_DEFAULT_VARIANT` override on a new view, if any
+    fn apply_env_variant(&mut self, view_id: i32) {
+        let Some(variant) = self.env_defaults.as_ref().and_then(|env| env.variant) else {
+            return;
+        };
+        // Only native glass has a variant
+        if self.views[&view_id].backend != GlassBackend::Native {
+            return;
+        }
+        if let Err(err) = self.set_variant_raw(view_id, variant) {
+            log::warn!(
+                "Failed to apply GLASS_DEFAULT_VARIANT to view {}: {}",
+                self.views[&view_id].label(view_id),
+                err
+            );
+        }
+    }
+
+    /// Instantiate the runtime class `name` for new glass views
+    ///
+    /// The class must be NSGlassEffectView or one of its subclasses.
+    pub fn set_glass_class_name(&mut self, name: &str) -> Result<()> {
+        let base = Class::get("NSGlassEffectView").ok_or(GlassError::UnsupportedPlatform)?;
+        let class = Class::get(name)
+            .ok_or_else(|| GlassError::ClassNotFound(name.to_string()))?;
+
+        let mut ancestor = Some(class);
+        while let Some(current) = ancestor {
+            if std::ptr::eq(current, base) {
+                self.glass_class = Some(class);
+                return Ok(());
+            }
+            ancestor = current.superclass();
+        }
+        Err(GlassError::InvalidClass(format!(
+            "{} is not a subclass of NSGlassEffectView",
+            name
+        )))
+    }
+
+    /// Class instantiated for glass views, if native glass is available
+    fn glass_class(&self) -> Option<&'static Class> {
+        self.glass_class.or_else(|| Class::get("NSGlassEffectView"))
+    }
+
+    /// Outline and label every managed view, including ones added later
+    pub fn set_debug_overlay(&mut self, enabled: bool) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        self.debug_overlay = enabled;
+        let view_ids: Vec<i32> = self.views.keys().copied().collect();
+        for view_id in view_ids {
+            self.refresh_debug_overlay(view_id);
+        }
+        Ok(())
+    }
+
+    /// Add or remove a view's debug overlay to match the manager setting
+    fn refresh_debug_overlay(&self, view_id: i32) {
+        let Some(record) = self.views.get(&view_id) else {
+            return;
+        };
+
+        unsafe {
+            for old in named_sublayers(record.view, DEBUG_LAYER_NAME) {
+                let _: () = msg_send![old, removeFromSuperlayer];
+            }
+            if self.debug_overlay {
+                add_debug_overlay(record.view, &record.label(view_id), view_id);
+            }
+        }
+    }
+
+    /// Choose what happens when adding glass to a container that already has some
+    pub fn set_dedup_policy(&mut self, policy: DedupPolicy) {
+        self.dedup_policy = policy;
+    }
+
+    /// Handle an existing managed view in `container` according to the dedup policy
+    fn apply_dedup_policy(&mut self, container: id) -> Result<()> {
+        if self.dedup_policy == DedupPolicy::Allow {
+            return Ok(());
+        }
+
+        match self.view_for_handle(container as *mut c_void) {
+            Some(view_id) if self.dedup_policy == DedupPolicy::Error => {
+                Err(GlassError::AlreadyExists(view_id))
+            }
+            Some(view_id) => self.remove_view(view_id),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail with `LimitExceeded` if `allocate_id` would, without taking an ID
+    ///
+    /// Called before creating native views so running out of IDs does not
+    /// leave unmanaged views behind in the container.
+    fn check_id_available(&self) -> Result<()> {
+        if self.free_ids.is_empty() && self.next_id.checked_add(1).is_none() {
+            return Err(GlassError::LimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Pick the ID for a new view, preferring the smallest freed ID
+    fn allocate_id(&mut self) -> Result<i32> {
+        if let Some(view_id) = self.free_ids.pop_first() {
+            return Ok(view_id);
+        }
+
+        let view_id = self.next_id;
+        self.next_id = self.next_id.checked_add(1).ok_or(GlassError::LimitExceeded)?;
+        Ok(view_id)
+    }
+
+    /// Return an error unless called on the main thread
+    unsafe fn ensure_main_thread(&self) -> Result<()> {
+        let current_thread: id = msg_send![class!(NSThread), currentThread];
+        let is_main: bool = msg_send![current_thread, isMainThread];
+        if !is_main {
+            return Err(GlassError::RuntimeError(
+                "Must be called from main thread".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fail unless called on the main thread
+    pub fn check_main_thread(&self) -> Result<()> {
+        unsafe { self.ensure_main_thread() }
+    }
+
+    /// Create a view for the given backend
+    unsafe fn create_view_for_backend(
+        &self,
+        backend: GlassBackend,
+        bounds: NSRect,
+        options: &GlassOptions,
+    ) -> Result<id> {
+        match backend {
+            GlassBackend::Native => unsafe { self.create_glass_view(bounds) }?
+                .ok_or(GlassError::UnsupportedPlatform),
+            GlassBackend::Fallback => unsafe { self.create_fallback_view(bounds) },
+            GlassBackend::SolidColor => unsafe { self.create_background_view(bounds) },
+            GlassBackend::BlurOnly => unsafe { self.create_blur_view(bounds, options.blur_radius) },
+        }
+    }
+
+    /// Replace a view's backend in place, keeping its ID and options
+    pub fn set_backend(&mut self, view_id: i32, backend: GlassBackend) -> Result<()> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        if record.backend == backend {
+            return Ok(());
+        }
+
+        if record.replaced_content.is_some() {
+            return Err(GlassError::RuntimeError(
+                "Cannot change the backend of a window content view".to_string(),
+            ));
+        }
+        if record.separator {
+            return Err(GlassError::InvalidState(
+                "separators have no backend to change".to_string(),
+            ));
+        }
+
+        unsafe {
+            self.ensure_main_thread()?;
+
+            let old_view = record.view;
+            let container: id = msg_send![old_view, superview];
+            let frame: NSRect = msg_send![old_view, frame];
+
+            let new_view = self.create_view_for_backend(backend, frame, &record.options)?;
+            self.configure_glass_view(new_view, &record.options)?;
+            self.apply_tint(new_view, &record.options, TintTiming::BeforeAttach);
+
+            // Carry over how the old view was sized and displayed
+            let mask: usize = msg_send![old_view, autoresizingMask];
+            let _: () = msg_send![new_view, setAutoresizingMask: mask];
+            let alpha: f64 = msg_send![old_view, alphaValue];
+            let _: () = msg_send![new_view, setAlphaValue: alpha];
+            let hidden: BOOL = msg_send![old_view, isHidden];
+            let _: () = msg_send![new_view, setHidden: hidden];
+            let appearance: id = msg_send![old_view, appearance];
+            let _: () = msg_send![new_view, setAppearance: appearance];
+            let old_layer: id = msg_send![old_view, layer];
+            if !old_layer.is_null() {
+                let z: f64 = msg_send![old_layer, zPosition];
+                let opacity: f32 = msg_send![old_layer, opacity];
+                let range_sel = sel!(preferredDynamicRange);
+                let has_range: bool = msg_send![old_layer, respondsToSelector: range_sel];
+                let range: id = if has_range { msg_send![old_layer, preferredDynamicRange] } else { nil };
+
+                let _: () = msg_send![new_view, setWantsLayer: YES];
+                let layer: id = msg_send![new_view, layer];
+                if z != 0.0 {
+                    let _: () = msg_send![layer, setZPosition: z];
+                }
+                if opacity != 1.0 {
+                    let _: () = msg_send![layer, setOpacity: opacity];
+                }
+                if !range.is_null() {
+                    let _: () = msg_send![layer, setPreferredDynamicRange: range];
+                }
+            }
+            if let Some(emphasized) = record.emphasized {
+                let responds: bool = msg_send![new_view, respondsToSelector: sel!(setEmphasized:)];
+                if responds {
+                    let _: () = msg_send![new_view, setEmphasized: emphasized];
+                }
+            }
+            if let Some(content) = record.content {
+                embed_content(new_view, content);
+            }
+
+            // Insert directly above the old view so z-order is preserved
+            if !container.is_null() {
+                let positioned = 1_isize; // NSWindowAbove
+                let _: () = msg_send![
+                    container,
+                    addSubview: new_view
+                    positioned: positioned
+                    relativeTo: old_view
+                ];
+                self.apply_tint(new_view, &record.options, TintTiming::AfterAttach);
+
+                // The old view's constraints go away with it
+                if let Some(pin) = record.options.pin {
+                    pin_to_container(new_view, container, pin);
+                    if let Some(ratio) = record.options.aspect_ratio {
+                        constrain_aspect_ratio(new_view, ratio);
+                    }
+                }
+            }
+            let _: () = msg_send![old_view, removeFromSuperview];
+
+            // Move hover tracking over to the new view
+            if let Some(ref hover) = record.hover {
+                let _: () = msg_send![old_view, removeTrackingArea: hover.area];
+                let _: () = msg_send![new_view, addTrackingArea: hover.area];
+            }
+
+            if let Some(mode) = record.blending_mode {
+                apply_blending_mode(new_view, mode);
+            }
+
+            let record = self.views.get_mut(&view_id).unwrap();
+            record.view = new_view;
+            record.backend = backend;
+            if let Some(ref mut observer) = record.appearance_observer {
+                observer.rebind(new_view);
+            }
+
+            // Balance the alloc / init that created the old view
+            let _: () = msg_send![old_view, release];
+        }
+
+        // Only native glass has a variant and states to restore
+        let record = &self.views[&view_id];
+        if backend == GlassBackend::Native {
+            let (variant, scrim_state, subdued_state, thickness) =
+                (record.variant, record.scrim_state, record.subdued_state, record.thickness);
+            let mut restored = Ok(());
+            if let Some(thickness) = thickness {
+                restored = restored.and(self.set_thickness(view_id, thickness));
+            }
+            if let Some(variant) = variant {
+                restored = restored.and(self.set_variant(view_id, variant));
+            }
+            if let Some(state) = scrim_state {
+                restored = restored.and(self.set_scrim_state(view_id, state));
+            }
+            if let Some(state) = subdued_state {
+                restored = restored.and(self.set_subdued_state(view_id, state));
+            }
+            if let Err(err) = restored {
+                log::warn!(
+                    "Failed to restore the variant, states and thickness of view {}: {}",
+                    self.views[&view_id].label(view_id),
+                    err
+                );
+            }
+        }
+        self.refresh_debug_overlay(view_id);
+
+        Ok(())
+    }
+
+    /// Create an NSGlassEffectView if available
+    unsafe fn create_glass_view(&self, bounds: NSRect) -> Result<Option<id>> {
+        if let Some(glass_class) = self.glass_class() {
+            let instance: id = msg_send![glass_class, alloc];
+            let instance: id = msg_send![instance, initWithFrame: bounds];
+            
+            if !instance.is_null() {
+                // Enable autoresizing (NSViewWidthSizable | NSViewHeightSizable)
+                let mask: usize = 2 | 16;
+                let _: () = msg_send![instance, setAutoresizingMask: mask];
+                return Ok(Some(instance));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Create fallback NSVisualEffectView
+    unsafe fn create_fallback_view(&self, bounds: NSRect) -> Result<id> {
+        let visual = unsafe { NSVisualEffectView::alloc(nil) };
+        let visual: id = msg_send![visual, initWithFrame: bounds];
+        
+        if visual.is_null() {
+            return Err(GlassError::CreationFailed);
+        }
+
+        // Configure visual effect view
+        // blendingMode = 0 (behindWindow)
+        let _: () = msg_send![visual, setBlendingMode: 0_isize];
+        // material = 0 (underWindowBackground)  
+        let _: () = msg_send![visual, setMaterial: 0_isize];
+        // state = 1 (active)
+        let _: () = msg_send![visual, setState: 1_isize];
+        
+        // Enable autoresizing
+        let mask: usize = 2 | 16;
+        let _: () = msg_send![visual, setAutoresizingMask: mask];
+
+        Ok(visual)
+    }
+
+    /// Create a view that only blurs its backdrop, with no material on top
+    unsafe fn create_blur_view(&self, bounds: NSRect, radius: f64) -> Result<id> {
+        // Private, but what NSVisualEffectView itself uses to sample the backdrop
+        let backdrop_class = Class::get("CABackdropLayer").ok_or(GlassError::CreationFailed)?;
+
+        let view: id = msg_send![class!(NSView), alloc];
+        let view: id = msg_send![view, initWithFrame: bounds];
+        if view.is_null() {
+            return Err(GlassError::CreationFailed);
+        }
+
+        let backdrop: id = msg_send![backdrop_class, layer];
+        let name = unsafe { NSString::alloc(nil).init_str("CIGaussianBlur") };
+        let blur: id = msg_send![class!(CIFilter), filterWithName: name];
+        let _: () = msg_send![name, release];
+        if backdrop.is_null() || blur.is_null() {
+            let _: () = msg_send![view, release];
+            return Err(GlassError::CreationFailed);
+        }
+
+        let radius: id = msg_send![class!(NSNumber), numberWithDouble: radius.max(0.0)];
+        let key = unsafe { NSString::alloc(nil).init_str("inputRadius") };
+        let _: () = msg_send![blur, setValue: radius forKey: key];
+        let _: () = msg_send![key, release];
+        let filters: id = msg_send![class!(NSArray), arrayWithObject: blur];
+        let _: () = msg_send![backdrop, setFilters: filters];
+
+        // Host the backdrop layer directly
+        let _: () = msg_send![view, setLayerUsesCoreImageFilters: YES];
+        let _: () = msg_send![view, setLayer: backdrop];
+        let _: () = msg_send![view, setWantsLayer: YES];
+
+        let mask: usize = 2 | 16;
+        let _: () = msg_send![view, setAutoresizingMask: mask];
+
+        Ok(view)
+    }
+
+    /// Create opaque background view
+    unsafe fn create_background_view(&self, bounds: NSRect) -> Result<id> {
+        let box_class = Class::get("NSBox").ok_or(GlassError::CreationFailed)?;
+        let bg: id = msg_send![box_class, alloc];
+        let bg: id = msg_send![bg, initWithFrame: bounds];
+        
+        if bg.is_null() {
+            return Err(GlassError::CreationFailed);
+        }
+
+        // Configure box
+        let _: () = msg_send![bg, setBoxType: 4_isize]; // NSBoxCustom
+        let _: () = msg_send![bg, setBorderType: 0_isize]; // NSNoBorder
+        
+        // Set background color
+        let window_bg_class = Class::get("NSColor").ok_or(GlassError::CreationFailed)?;
+        let window_bg_color: id = msg_send![window_bg_class, windowBackgroundColor];
+        let _: () = msg_send![bg, setFillColor: window_bg_color];
+        
+        // Enable layer and autoresizing
+        let _: () = msg_send![bg, setWantsLayer: YES];
+        let mask: usize = 2 | 16;
+        let _: () = msg_send![bg, setAutoresizingMask: mask];
+
+        Ok(bg)
+    }
+
+    /// Place the shadow view for a rounded, shadowed glass view beneath it
+    ///
+    /// Reuses `existing` if given; returns `None` when no separate view is needed.
+    unsafe fn attach_shadow_view(
+        &self,
+        glass_view: id,
+        existing: Option<id>,
+        options: &GlassOptions,
+    ) -> Result<Option<id>> {
+        let shadow = match options.shadow {
+            Some(shadow) if options.corner_radius > 0.0 => shadow,
+            _ => return Ok(None),
+        };
+
+        let container: id = msg_send![glass_view, superview];
+        if container.is_null() {
+            return Ok(existing);
+        }
+
+        let frame: NSRect = msg_send![glass_view, frame];
+        let shadow_view = match existing {
+            Some(shadow_view) => shadow_view,
+            None => {
+                let shadow_view: id = msg_send![class!(NSView), alloc];
+                let shadow_view: id = msg_send![shadow_view, initWithFrame: frame];
+                if shadow_view.is_null() {
+                    return Err(GlassError::CreationFailed);
+                }
+
+                let mask: usize = msg_send![glass_view, autoresizingMask];
+                let _: () = msg_send![shadow_view, setAutoresizingMask: mask];
+                let _: () = msg_send![shadow_view, setWantsLayer: YES];
+                let layer: id = msg_send![shadow_view, layer];
+                unsafe { apply_shadow(layer, shadow) };
+                shadow_view
+            }
+        };
+
+        let _: () = msg_send![shadow_view, setFrame: frame];
+        unsafe { update_shadow_path(shadow_view, options.corner_radius) };
+        unsafe { self.add_subview(container, shadow_view, glass_view)? };
+
+        Ok(Some(shadow_view))
+    }
+
+    /// Add subview with positioning
+    unsafe fn add_subview(
+        &self,
+        container: id,
+        subview: id,
+        relative_to: id,
+    ) -> Result<()> {
+        let positioned = -1_isize; // NSWindowBelow
+        
+        let _: () = msg_send![
+            container,
+            addSubview: subview
+            positioned: positioned
+            relativeTo: relative_to
+        ];
+        
+        Ok(())
+    }
+
+    /// Configure glass view with options
+    unsafe fn configure_glass_view(&self, view: id, options: &GlassOptions) -> Result<()> {
+        // All class hooks go in at once, the first time a view is configured,
+        // before anything observes it: swizzling a view's class after KVO has
+        // replaced it breaks the observation
+        unsafe { install_glass_hooks(view) };
+
+        if let Some(ref identifier) = options.identifier {
+            let identifier = unsafe { NSString::alloc(nil).init_str(identifier) };
+            let _: () = msg_send![view, setIdentifier: identifier];
+            let _: () = msg_send![identifier, release];
+        }
+
+        // Remember the view's own layer backing so an override can be undone
+        if unsafe { stored_flag(view, &DEFAULT_WANTS_LAYER_KEY) }.is_none() {
+            let wants_layer: BOOL = msg_send![view, wantsLayer];
+            unsafe { store_flag(view, &DEFAULT_WANTS_LAYER_KEY, Some(wants_layer == YES)) };
+        }
+        if let Some(wants_layer) = options.wants_layer {
+            let _: () = msg_send![view, setWantsLayer: if wants_layer { YES } else { NO }];
+        }
+
+        // Set corner radius
+        if options.corner_radius > 0.0 {
+            unsafe { apply_corner_radius(view, options.corner_radius, options.round_corners) };
+        }
+
+        // Without a corner radius nothing clips, so the glass casts its own
+        // shadow; otherwise `attach_shadow_view` adds one beneath it
+        if let Some(shadow) = options.shadow && options.corner_radius <= 0.0 {
+            let _: () = msg_send![view, setWantsLayer: YES];
+            let layer: id = msg_send![view, layer];
+            unsafe { apply_shadow(layer, shadow) };
+        }
+
+        // Keep the container out of (or explicitly in) the key view loop, and
+        // override whether content inside it blends vibrantly. The hooks read
+        // these per view, so later updates only change the stored flags.
+        unsafe {
+            store_flag(view, &FOCUSABLE_KEY, Some(options.accepts_first_responder));
+            store_flag(view, &VIBRANCY_KEY, options.allows_vibrancy);
+        }
+
+
+        unsafe {
+            self.apply_tint_layers(view, options);
+            apply_grain(view, options);
+        }
+
+        // Thin highlight along the top edge
+        if let Some(ref rim) = options.rim_highlight
+            && let Ok(color) = unsafe { self.parse_hex_color(rim) }
+        {
+            unsafe { apply_rim_highlight(view, color, options.corner_radius, options.round_corners) };
+        }
+
+        Ok(())
+    }
+
+    /// Apply the tint option if the view's `tint_timing` is `timing`
+    unsafe fn apply_tint(&self, view: id, options: &GlassOptions, timing: TintTiming) {
+        if options.tint_timing != timing {
+            return;
+        }
+
+        let sel = sel!(setTintColor:);
+        let responds: bool = msg_send![view, respondsToSelector: sel];
+        let fills: bool = msg_send![view, respondsToSelector: sel!(setFillColor:)];
+        // The layer background renders differently, so it may have its own color
+        let tint = if responds || fills {
+            options.tint_color.as_ref()
+        } else {
+            options.fallback_tint_color.as_ref().or(options.tint_color.as_ref())
+        };
+
+        if let Some(tint) = tint {
+            if let Ok(color) = unsafe { self.parse_hex_color(tint) } {
+                // Try to set tintColor using runtime
+                if responds {
+                    let _: () = msg_send![view, setTintColor: color];
+                } else if fills {
+                    // Solid color views use the tint as their fill
+                    let _: () = msg_send![view, setFillColor: color];
+                } else {
+                    let layer: id = msg_send![view, layer];
+                    if !layer.is_null() {
+                        // Fallback to layer backgroundColor
+                        let cg_color: id = msg_send![color, CGColor];
+                        let _: () = msg_send![layer, setBackgroundColor: cg_color];
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replace a view's tint layers with those in `options`
+    ///
+    /// Layers are stacked in order above the glass but below the rim
+    /// highlight, each clipped to the view's rounded corners.
+    unsafe fn apply_tint_layers(&self, view: id, options: &GlassOptions) {
+        for old in unsafe { named_sublayers(view, TINT_LAYER_NAME) } {
+            let _: () = msg_send![old, removeFromSuperlayer];
+        }
+        if options.tint_layers.is_empty() {
+            return;
+        }
+
+        let _: () = msg_send![view, setWantsLayer: YES];
+        let layer: id = msg_send![view, layer];
+        if layer.is_null() {
+            return;
+        }
+        let rim = unsafe { rim_layer(view) };
+
+        for tint in &options.tint_layers {
+            let Ok(color) = (unsafe { self.parse_hex_color(&tint.color) }) else {
+                continue;
+            };
+
+            let sublayer: id = msg_send![class!(CALayer), layer];
+            let name = unsafe { NSString::alloc(nil).init_str(TINT_LAYER_NAME) };
+            let _: () = msg_send![sublayer, setName: name];
+            let _: () = msg_send![name, release];
+
+            let cg_color: id = msg_send![color, CGColor];
+            let _: () = msg_send![sublayer, setBackgroundColor: cg_color];
+            if let Some(filter) = tint.blend_mode.compositing_filter() {
+                let filter = unsafe { NSString::alloc(nil).init_str(filter) };
+                let _: () = msg_send![sublayer, setCompositingFilter: filter];
+                let _: () = msg_send![filter, release];
+            }
+            let _: () = msg_send![sublayer, setCornerRadius: options.corner_radius];
+            let _: () = msg_send![sublayer, setMaskedCorners: options.round_corners.bits() as usize];
+            let _: () = msg_send![sublayer, setMasksToBounds: YES];
+            // kCALayerWidthSizable | kCALayerHeightSizable
+            let resizing: u32 = 2 | 16;
+            let _: () = msg_send![sublayer, setAutoresizingMask: resizing];
+
+            match rim {
+                Some(rim) => {
+                    let _: () = msg_send![layer, insertSublayer: sublayer below: rim];
+                }
+                None => {
+                    let _: () = msg_send![layer, addSublayer: sublayer];
+                }
+            }
+        }
+
+        unsafe { layout_overlay_layers(view) };
+    }
+
+    /// Parse hex color string to NSColor
+    unsafe fn parse_hex_color(&self, hex: &str) -> Result<id> {
+        let (r, g, b, a) = parse_hex_rgba(hex)?;
+
+        let color = unsafe { NSColor::colorWithSRGBRed_green_blue_alpha_(nil, r, g, b, a) };
+        if color.is_null() {
+            return Err(GlassError::InvalidColor(hex.to_string()));
+        }
+        Ok(color)
+    }
+
+    /// Whether a view was given a tint color or tint layers
+    pub fn has_tint(&self, view_id: i32) -> Result<bool> {
+        let options = &self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .options;
+
+        Ok(options.tint_color.is_some() || !options.tint_layers.is_empty())
+    }
+
+    /// Read back the tint applied to a view as sRGB components
+    pub fn resolved_tint(&self, view_id: i32) -> Result<Option<(f64, f64, f64, f64)>> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        if record.options.tint_color.is_none() {
+            return Ok(None);
+        }
+
+        unsafe {
+            let view = record.view;
+            let has_tint: bool = msg_send![view, respondsToSelector: sel!(tintColor)];
+            let has_fill: bool = msg_send![view, respondsToSelector: sel!(fillColor)];
+
+            // Mirror the order configure_glass_view applies the tint in
+            let color: id = if has_tint {
+                msg_send![view, tintColor]
+            } else if has_fill {
+                msg_send![view, fillColor]
+            } else {
+                let layer: id = msg_send![view, layer];
+                if layer.is_null() {
+                    nil
+                } else {
+                    let cg_color: *const c_void = msg_send![layer, backgroundColor];
+                    if cg_color.is_null() {
+                        nil
+                    } else {
+                        msg_send![class!(NSColor), colorWithCGColor: cg_color]
+                    }
+                }
+            };
+            if color.is_null() {
+                return Ok(None);
+            }
+
+            let srgb: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+            let color: id = msg_send![color, colorUsingColorSpace: srgb];
+            if color.is_null() {
+                return Ok(None);
+            }
+
+            let r: f64 = msg_send![color, redComponent];
+            let g: f64 = msg_send![color, greenComponent];
+            let b: f64 = msg_send![color, blueComponent];
+            let a: f64 = msg_send![color, alphaComponent];
+            Ok(Some((r, g, b, a)))
+        }
+    }
+
+    /// Tint a view with `base_hex`, adjusted for contrast with its backdrop
+    pub fn set_adaptive_tint(&mut self, view_id: i32, base_hex: &str, contrast: f64) -> Result<()> {
+        // Only remember the adaptive tint once it has been applied
+        self.apply_adaptive_tint(view_id, base_hex, contrast)?;
+        self.views.get_mut(&view_id).unwrap().adaptive_tint = Some((base_hex.to_string(), contrast));
+        Ok(())
+    }
+
+    /// Re-sample the backdrop and update an adaptive tint
+    pub fn refresh_adaptive_tint(&mut self, view_id: i32) -> Result<()> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        let (base_hex, contrast) = record.adaptive_tint.clone().ok_or_else(|| {
+            GlassError::InvalidState(format!(
+                "view {} has no adaptive tint",
+                record.label(view_id)
+            ))
+        })?;
+
+        self.apply_adaptive_tint(view_id, &base_hex, contrast)
+    }
+
+    /// Sample the backdrop and tint a view with `base_hex` adjusted for it
+    fn apply_adaptive_tint(&mut self, view_id: i32, base_hex: &str, contrast: f64) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let base = parse_hex_rgba(base_hex)?;
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        let tint = match unsafe { backdrop_luminance(record.view) } {
+            Some(luminance) => adapt_tint(base, luminance, contrast),
+            None => base,
+        };
+
+        let record = self.views.get_mut(&view_id).unwrap();
+        record.options.tint_color = Some(format_hex_rgba(tint));
+
+        let record = &self.views[&view_id];
+        unsafe { self.apply_tint(record.view, &record.options, record.options.tint_timing) };
+        Ok(())
+    }
+
+    /// Mute a view to look disabled, or restore its normal look
+    pub fn set_enabled_appearance(&mut self, view_id: i32, enabled: bool) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        if record.disabled_alpha.is_some() != enabled {
+            return Ok(());
+        }
+        let view = record.view;
+
+        if enabled {
+            let record = self.views.get_mut(&view_id).unwrap();
+            let alpha = record.disabled_alpha.take().unwrap();
+            let subdued_state = record.subdued_state.unwrap_or(0);
+            let record = &self.views[&view_id];
+            unsafe {
+                let _: () = msg_send![view, setAlphaValue: alpha];
+                self.apply_tint(view, &record.options, record.options.tint_timing);
+            }
+            // Not every backend has a subdued state
+            let _ = self.set_int_property(view_id, "subduedState", subdued_state);
+        } else {
+            let alpha: f64 = unsafe { msg_send![view, alphaValue] };
+            let _: () = unsafe { msg_send![view, setAlphaValue: alpha * DISABLED_ALPHA_FACTOR] };
+            let _ = self.set_int_property(view_id, "subduedState", 1);
+
+            // Tint with a greyed copy of the options, keeping the originals
+            if let Some(options) = disabled_tint_options(&record.options) {
+                unsafe { self.apply_tint(view, &options, options.tint_timing) };
+            }
+
+            self.views.get_mut(&view_id).unwrap().disabled_alpha = Some(alpha);
+        }
+
+        Ok(())
+    }
+
+    /// Backing scale factor of the view's window (or the main screen)
+    pub fn backing_scale(&self, view_id: i32) -> Result<f64> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        Ok(unsafe { backing_scale(view) })
+    }
+
+    /// Whether the view's layer clips its contents (`masksToBounds`)
+    pub fn is_clipping(&self, view_id: i32) -> Result<bool> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            let layer: id = msg_send![view, layer];
+            Ok(!layer.is_null() && msg_send![layer, masksToBounds])
+        }
+    }
+
+    /// Set the zPosition of the view's layer, and of every view stacked with it
+    pub fn set_z_position(&self, view_id: i32, z: f64) -> Result<()> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        unsafe {
+            self.ensure_main_thread()?;
+
+            // Keep the shadow, background and snapshot next to the glass they
+            // belong to; equal z falls back to subview order between them
+            for view in [
+                Some(record.view),
+                record.shadow_view,
+                record.background,
+                record.background_image,
+                record.snapshot,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let _: () = msg_send![view, setWantsLayer: YES];
+                let layer: id = msg_send![view, layer];
+                if !layer.is_null() {
+                    let _: () = msg_send![layer, setZPosition: z];
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Choose whether the view's layer may render extended dynamic range content
+    pub fn set_edr_enabled(&self, view_id: i32, enabled: bool) -> Result<()> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            self.ensure_main_thread()?;
+
+            let _: () = msg_send![view, setWantsLayer: YES];
+            let layer: id = msg_send![view, layer];
+            let responds: bool = !layer.is_null()
+                && msg_send![layer, respondsToSelector: sel!(setPreferredDynamicRange:)];
+            if !responds {
+                return Err(GlassError::RuntimeError(
+                    "Dynamic range control requires macOS 14 or later".to_string(),
+                ));
+            }
+
+            // CADynamicRangeHigh / CADynamicRangeStandard
+            let range = NSString::alloc(nil).init_str(if enabled { "high" } else { "standard" });
+            let _: () = msg_send![layer, setPreferredDynamicRange: range];
+            let _: () = msg_send![range, release];
+        }
+
+        Ok(())
+    }
+
+    /// Set glass material variant
+    pub fn set_variant(&mut self, view_id: i32, variant: GlassMaterialVariant) -> Result<()> {
+        self.set_int_property(view_id, "variant", variant.raw())?;
+        self.views.get_mut(&view_id).unwrap().variant = Some(variant);
+        Ok(())
+    }
+
+    /// Set the variant after the current one, wrapping around
+    pub fn cycle_variant(&mut self, view_id: i32) -> Result<GlassMaterialVariant> {
+        let current = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .variant
+            .unwrap_or(GlassMaterialVariant::Regular);
+
+        // Values without a named variant start over from the first one
+        let all = GlassMaterialVariant::all();
+        let next = match all.iter().position(|&variant| variant == current) {
+            Some(index) => all[(index + 1) % all.len()],
+            None => GlassMaterialVariant::Regular,
+        };
+        self.set_variant(view_id, next)?;
+        Ok(next)
+    }
+
+    /// Set the variant from a raw value, which need not be a known variant
+    pub fn set_variant_raw(&mut self, view_id: i32, value: i64) -> Result<()> {
+        self.set_int_property(view_id, "variant", value)?;
+        let variant = GlassMaterialVariant::from_raw(value)
+            .unwrap_or(GlassMaterialVariant::Unknown(value));
+        self.views.get_mut(&view_id).unwrap().variant = Some(variant);
+        Ok(())
+    }
+
+    /// Change the corner radius of a view instantly
+    pub fn set_corner_radius(&mut self, view_id: i32, radius: f64) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get_mut(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        let radius = radius.max(0.0);
+        record.options.corner_radius = radius;
+
+        unsafe {
+            if radius > 0.0 {
+                apply_corner_radius(record.view, radius, record.options.round_corners);
+            } else {
+                store_corner_mask(record.view, None);
+                let layer: id = msg_send![record.view, layer];
+                if !layer.is_null() {
+                    let _: () = msg_send![layer, setCornerRadius: 0.0_f64];
+                }
+                let is_visual_effect: bool =
+                    msg_send![record.view, isKindOfClass: class!(NSVisualEffectView)];
+                if is_visual_effect {
+                    let _: () = msg_send![record.view, setMaskImage: nil];
+                }
+            }
+
+            // A background without its own radius follows the glass
+            if let Some(bg) = record.background
+                && record.options.background_corner_radius.is_none()
+            {
+                round_background(bg, &record.options);
+            }
+            if let Some(shadow_view) = record.shadow_view {
+                update_shadow_path(shadow_view, radius);
+            }
+            if let Some(rim) = rim_layer(record.view) {
+                let _: () = msg_send![rim, setCornerRadius: radius];
+                layout_rim_highlight(record.view);
+            }
+            for overlay in overlay_layers(record.view) {
+                let _: () = msg_send![overlay, setCornerRadius: radius];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the scrim state
+    pub fn set_scrim_state(&mut self, view_id: i32, state: i64) -> Result<()> {
+        self.set_int_property(view_id, "scrimState", state)?;
+        self.views.get_mut(&view_id).unwrap().scrim_state = Some(state);
+        Ok(())
+    }
+
+    /// Set the subdued state
+    pub fn set_subdued_state(&mut self, view_id: i32, state: i64) -> Result<()> {
+        self.set_int_property(view_id, "subduedState", state)?;
+        self.views.get_mut(&view_id).unwrap().subdued_state = Some(state);
+        Ok(())
+    }
+
+    /// Capture the restorable state of every view, ordered by ID
+    #[cfg(feature = "serde")]
+    pub fn view_states(&self) -> Vec<ViewState> {
+        let mut states: Vec<ViewState> = self
+            .views
+            .iter()
+            .map(|(&id, record)| ViewState {
+                id,
+                options: record.options.clone(),
+                variant: record.variant,
+                scrim_state: record.scrim_state,
+                subdued_state: record.subdued_state,
+            })
+            .collect();
+        states.sort_by_key(|state| state.id);
+        states
+    }
+
+    /// Add a view with the same options, variant and states to another container
+    pub fn duplicate(&mut self, view_id: i32, new_container: *mut c_void) -> Result<i32> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        let options = record.options.clone();
+        let (variant, scrim_state, subdued_state) =
+            (record.variant, record.scrim_state, record.subdued_state);
+        self.add_with_states(new_container, options, variant, scrim_state, subdued_state)
+    }
+
+    /// Recreate a view from saved state on a container
+    #[cfg(feature = "serde")]
+    pub fn restore_view_state(&mut self, state: ViewState, container: *mut c_void) -> Result<i32> {
+        self.add_with_states(
+            container,
+            state.options,
+            state.variant,
+            state.scrim_state,
+            state.subdued_state,
+        )
+    }
+
+    /// Add a view and set its variant and states, removing it again on failure
+    fn add_with_states(
+        &mut self,
+        container: *mut c_void,
+        options: GlassOptions,
+        variant: Option<GlassMaterialVariant>,
+        scrim_state: Option<i64>,
+        subdued_state: Option<i64>,
+    ) -> Result<i32> {
+        let view_id = self.add_glass_view(container, options)?;
+        let result = (|| {
+            if let Some(variant) = variant {
+                self.set_variant(view_id, variant)?;
+            }
+            if let Some(scrim) = scrim_state {
+                self.set_scrim_state(view_id, scrim)?;
+            }
+            if let Some(subdued) = subdued_state {
+                self.set_subdued_state(view_id, subdued)?;
+            }
+            Ok(view_id)
+        })();
+
+        if result.is_err() {
+            let _ = self.remove_view(view_id);
+        }
+        result
+    }
+
+    /// Install pointer enter/exit callbacks on a view, replacing any existing ones
+    pub fn set_hover_callbacks(
+        &mut self,
+        view_id: i32,
+        on_enter: Box<dyn Fn()>,
+        on_exit: Box<dyn Fn()>,
+    ) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get_mut(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        unsafe {
+            if let Some(old) = record.hover.take() {
+                remove_hover_tracking(record.view, old);
+            }
+
+            let callbacks = Box::new(HoverCallbacks { on_enter, on_exit });
+
+            let target: id = msg_send![hover_target_class(), new];
+            if target.is_null() {
+                return Err(GlassError::CreationFailed);
+            }
+            (*target).set_ivar::<*mut c_void>(
+                HOVER_CALLBACKS_IVAR,
+                &*callbacks as *const HoverCallbacks as *mut c_void,
+            );
+
+            // NSTrackingMouseEnteredAndExited | NSTrackingActiveAlways | NSTrackingInVisibleRect
+            let tracking_options: usize = 0x01 | 0x80 | 0x200;
+            let bounds: NSRect = msg_send![record.view, bounds];
+            let area: id = msg_send![class!(NSTrackingArea), alloc];
+            let area: id = msg_send![
+                area,
+                initWithRect: bounds
+                options: tracking_options
+                owner: target
+                userInfo: nil
+            ];
+            if area.is_null() {
+                let _: () = msg_send![target, release];
+                return Err(GlassError::CreationFailed);
+            }
+            let _: () = msg_send![record.view, addTrackingArea: area];
+
+            record.hover = Some(HoverTracking {
+                area,
+                target,
+                _callbacks: callbacks,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Call `on_change` whenever the view's effective appearance switches
+    /// between light and dark, replacing any existing callback
+    pub fn set_effective_appearance_callback(
+        &mut self,
+        view_id: i32,
+        on_change: Box<dyn Fn(Appearance)>,
+    ) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get_mut(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        // KVO also fires for changes that keep the view light or dark. The
+        // last appearance is tracked while paused so resuming does not replay
+        let last = Cell::new(unsafe { effective_appearance(record.view) });
+        let enabled = record.observers_enabled.clone();
+        let on_change = Box::new(move |appearance: Appearance| {
+            if last.replace(appearance) != appearance && enabled.get() {
+                on_change(appearance);
+            }
+        });
+
+        // Drop the old observer first so only one is registered at a time
+        record.appearance_observer = None;
+        let observer = unsafe { AppearanceObserver::new(record.view, on_change) }
+            .ok_or(GlassError::CreationFailed)?;
+        record.appearance_observer = Some(observer);
+        Ok(())
+    }
+
+    /// Install an image beneath the glass so it blurs the image instead of the desktop
+    pub fn set_background_image(&mut self, view_id: i32, png_bytes: &[u8]) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            let data: id = msg_send![
+                class!(NSData),
+                dataWithBytes: png_bytes.as_ptr() as *const c_void
+                length: png_bytes.len()
+            ];
+            let image: id = msg_send![class!(NSImage), alloc];
+            let image: id = msg_send![image, initWithData: data];
+            if image.is_null() {
+                return Err(GlassError::InvalidImage);
+            }
+
+            let container: id = msg_send![view, superview];
+            if container.is_null() {
+                let _: () = msg_send![image, release];
+                return Err(GlassError::RuntimeError(
+                    "View is not attached to a container".to_string(),
+                ));
+            }
+
+            let frame: NSRect = msg_send![view, frame];
+            let image_view: id = msg_send![class!(NSImageView), alloc];
+            let image_view: id = msg_send![image_view, initWithFrame: frame];
+            if image_view.is_null() {
+                let _: () = msg_send![image, release];
+                return Err(GlassError::CreationFailed);
+            }
+            let _: () = msg_send![image_view, setImage: image];
+            let _: () = msg_send![image, release];
+            // NSImageScaleAxesIndependently
+            let _: () = msg_send![image_view, setImageScaling: 1_isize];
+            let mask: usize = 2 | 16;
+            let _: () = msg_send![image_view, setAutoresizingMask: mask];
+
+            self.add_subview(container, image_view, view)?;
+            apply_blending_mode(view, BlendingMode::WithinWindow);
+
+            let record = self.views.get_mut(&view_id).unwrap();
+            record.blending_mode = Some(BlendingMode::WithinWindow);
+            if let Some(old) = record.background_image.replace(image_view) {
+                let _: () = msg_send![old, removeFromSuperview];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Cover the live view with a static snapshot of its current rendering
+    pub fn freeze(&mut self, view_id: i32) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        if record.snapshot.is_some() {
+            return Ok(());
+        }
+        let view = record.view;
+
+        unsafe {
+            let container: id = msg_send![view, superview];
+            if container.is_null() {
+                return Err(GlassError::RuntimeError(
+                    "View is not attached to a container".to_string(),
+                ));
+            }
+
+            let bounds: NSRect = msg_send![view, bounds];
+            let rep: id = msg_send![view, bitmapImageRepForCachingDisplayInRect: bounds];
+            if rep.is_null() {
+                return Err(GlassError::CreationFailed);
+            }
+            let _: () = msg_send![view, cacheDisplayInRect: bounds toBitmapImageRep: rep];
+
+            let image: id = msg_send![class!(NSImage), alloc];
+            let image: id = msg_send![image, initWithSize: bounds.size];
+            let _: () = msg_send![image, addRepresentation: rep];
+
+            let frame: NSRect = msg_send![view, frame];
+            let image_view: id = msg_send![class!(NSImageView), alloc];
+            let image_view: id = msg_send![image_view, initWithFrame: frame];
+            if image_view.is_null() {
+                let _: () = msg_send![image, release];
+                return Err(GlassError::CreationFailed);
+            }
+            let _: () = msg_send![image_view, setImage: image];
+            let _: () = msg_send![image, release];
+            // NSImageScaleAxesIndependently
+            let _: () = msg_send![image_view, setImageScaling: 1_isize];
+            let mask: usize = msg_send![view, autoresizingMask];
+            let _: () = msg_send![image_view, setAutoresizingMask: mask];
+            let layer: id = msg_send![view, layer];
+            if !layer.is_null() {
+                let z: f64 = msg_send![layer, zPosition];
+                if z != 0.0 {
+                    let _: () = msg_send![image_view, setWantsLayer: YES];
+                    let snapshot_layer: id = msg_send![image_view, layer];
+                    let _: () = msg_send![snapshot_layer, setZPosition: z];
+                }
+            }
+
+            let positioned = 1_isize; // NSWindowAbove
+            let _: () = msg_send![
+                container,
+                addSubview: image_view
+                positioned: positioned
+                relativeTo: view
+            ];
+            // The container now owns the snapshot
+            let _: () = msg_send![image_view, release];
+            let _: () = msg_send![view, setHidden: YES];
+
+            self.views.get_mut(&view_id).unwrap().snapshot = Some(image_view);
+        }
+
+        Ok(())
+    }
+
+    /// Remove the snapshot added by `freeze` and show the live view again
+    pub fn unfreeze(&mut self, view_id: i32) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let record = self.views.get_mut(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        if let Some(snapshot) = record.snapshot.take() {
+            unsafe {
+                let _: () = msg_send![record.view, setHidden: NO];
+                let _: () = msg_send![snapshot, removeFromSuperview];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Choose whether a view blurs the desktop or in-window content
+    pub fn set_blending_mode(&mut self, view_id: i32, mode: BlendingMode) -> Result<()> {
+        let record = self.views.get_mut(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        if !unsafe { apply_blending_mode(record.view, mode) } {
+            return Err(GlassError::RuntimeError(format!(
+                "View {} does not support blending modes",
+                record.label(view_id)
+            )));
+        }
+
+        record.blending_mode = Some(mode);
+        Ok(())
+    }
+
+    /// Resize a view (and its background views) to fill its container
+    pub fn sync_bounds(&self, view_id: i32) -> Result<()> {
+        let record = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        // Views placed at an explicit frame, or laid out by constraints, keep it
+        if record.options.frame.is_some() || record.options.pin.is_some() {
+            return Ok(());
+        }
+
+        unsafe {
+            let container: id = msg_send![record.view, superview];
+            if container.is_null() {
+                return Ok(());
+            }
+            let bounds: NSRect = msg_send![container, bounds];
+
+            let _: () = msg_send![record.view, setFrame: bounds];
+            for extra in [
+                record.background,
+                record.background_image,
+                record.shadow_view,
+                record.snapshot,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let _: () = msg_send![extra, setFrame: bounds];
+            }
+            if let Some(shadow_view) = record.shadow_view {
+                update_shadow_path(shadow_view, record.options.corner_radius);
+            }
+            layout_rim_highlight(record.view);
+            layout_overlay_layers(record.view);
+        }
+
+        Ok(())
+    }
+
+    /// Queue a view for a coalesced bounds sync
+    ///
+    /// Returns `true` if this is the first pending request, meaning the caller
+    /// must schedule a call to `flush_bounds_sync`.
+    pub fn queue_bounds_sync(&mut self, view_id: i32) -> Result<bool> {
+        if !self.views.contains_key(&view_id) {
+            return Err(GlassError::InvalidViewId(view_id));
+        }
+
+        let first = self.pending_bounds_sync.is_empty();
+        self.pending_bounds_sync.insert(view_id);
+        Ok(first)
+    }
+
+    /// Sync the bounds of every view queued by `queue_bounds_sync`
+    pub fn flush_bounds_sync(&mut self) {
+        for view_id in std::mem::take(&mut self.pending_bounds_sync) {
+            // Views removed since the request are skipped
+            let _ = self.sync_bounds(view_id);
+        }
+    }
+
+    /// Check whether a view is layer-backed
+    pub fn is_layer_backed(&self, view_id: i32) -> Result<bool> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        let wants_layer: bool = unsafe { msg_send![view, wantsLayer] };
+        Ok(wants_layer)
+    }
+
+    /// Override a view's appearance, or inherit it again with `None`
+    pub fn set_appearance(&self, view_id: i32, appearance: Option<Appearance>) -> Result<()> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            let ns_appearance: id = match appearance {
+                Some(appearance) => {
+                    let name = NSString::alloc(nil).init_str(appearance.name());
+                    let ns_appearance: id = msg_send![class!(NSAppearance), appearanceNamed: name];
+                    let _: () = msg_send![name, release];
+                    ns_appearance
+                }
+                None => nil,
+            };
+            let _: () = msg_send![view, setAppearance: ns_appearance];
+        }
+
+        Ok(())
+    }
+
+    /// Pin a view to the appearance it currently resolves to
+    pub fn freeze_appearance(&self, view_id: i32) -> Result<Appearance> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        let appearance = unsafe { effective_appearance(view) };
+        self.set_appearance(view_id, Some(appearance))?;
+        Ok(appearance)
+    }
+
+    /// Set the view's alpha value
+    pub fn set_alpha(&self, view_id: i32, alpha: f64) -> Result<()> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            let _: () = msg_send![view, setAlphaValue: alpha.clamp(0.0, 1.0)];
+        }
+
+        Ok(())
+    }
+
+    /// Animate the view's alpha, calling `on_complete` when the animation ends
+    ///
+    /// The animation is instant when animations are not allowed.
+    pub fn animate_alpha(
+        &self,
+        view_id: i32,
+        alpha: f64,
+        duration: f64,
+        on_complete: impl FnOnce() + 'static,
+    ) -> Result<()> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        let duration = if self.animations_allowed() { duration.max(0.0) } else { 0.0 };
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        unsafe {
+            self.ensure_main_thread()?;
+
+            let changes = RcBlock::new(move |context: *mut objc2::runtime::AnyObject| {
+                let context = context as id;
+                let _: () = msg_send![context, setDuration: duration];
+                let animator: id = msg_send![view, animator];
+                let _: () = msg_send![animator, setAlphaValue: alpha];
+            });
+            let changes: &Block<dyn Fn(*mut objc2::runtime::AnyObject)> = &changes;
+
+            let on_complete = std::cell::Cell::new(Some(on_complete));
+            let completion = RcBlock::new(move || {
+                if let Some(on_complete) = on_complete.take() {
+                    on_complete();
+                }
+            });
+            let completion: &Block<dyn Fn()> = &completion;
+
+            let _: () = msg_send![
+                class!(NSAnimationContext),
+                runAnimationGroup: changes as *const _
+                completionHandler: completion as *const _
+            ];
+        }
+
+        Ok(())
+    }
+
+    /// Set the opacity of the view's backing layer
+    pub fn set_layer_opacity(&self, view_id: i32, opacity: f64) -> Result<()> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            let _: () = msg_send![view, setWantsLayer: YES];
+            let layer: id = msg_send![view, layer];
+            if layer.is_null() {
+                return Err(GlassError::RuntimeError("View has no layer".to_string()));
+            }
+            // CALayer.opacity is a float
+            let _: () = msg_send![layer, setOpacity: opacity.clamp(0.0, 1.0) as f32];
+        }
+
+        Ok(())
+    }
+
+    /// Get the view's current alpha value
+    pub fn get_alpha(&self, view_id: i32) -> Result<f64> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        let alpha: f64 = unsafe { msg_send![view, alphaValue] };
+        Ok(alpha)
+    }
+
+    /// Set the emphasized state of a view
+    pub fn set_emphasized(&mut self, view_id: i32, emphasized: bool) -> Result<()> {
+        let record = self.views.get_mut(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        unsafe {
+            let responds: bool = msg_send![record.view, respondsToSelector: sel!(setEmphasized:)];
+            if responds {
+                let _: () = msg_send![record.view, setEmphasized: emphasized];
+                record.emphasized = Some(emphasized);
+            } else {
+                log::warn!(
+                    "View {} does not support the emphasized state, ignoring",
+                    record.label(view_id)
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Clip the containing window's content view to rounded corners
+    pub fn round_window_corners(&self, view_id: i32, radius: f64) -> Result<()> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            self.ensure_main_thread()?;
+
+            let window: id = msg_send![view, window];
+            if window.is_null() {
+                return Err(GlassError::RuntimeError(
+                    "View is not attached to a window".to_string(),
+                ));
+            }
+
+            let content_view: id = msg_send![window, contentView];
+            if content_view.is_null() {
+                return Err(GlassError::RuntimeError(
+                    "Window has no content view".to_string(),
+                ));
+            }
+
+            let _: () = msg_send![content_view, setWantsLayer: YES];
+            let layer: id = msg_send![content_view, layer];
+            if !layer.is_null() {
+                let _: () = msg_send![layer, setCornerRadius: radius];
+                let _: () = msg_send![layer, setMasksToBounds: YES];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of views currently managed
+    pub fn view_count(&self) -> usize {
+        self.views.len()
+    }
+
+    /// Rough memory estimate for all managed views
+    pub fn estimated_footprint(&self) -> FootprintEstimate {
+        let mut estimate = FootprintEstimate {
+            views: self.views.len(),
+            ..Default::default()
+        };
+        for record in self.views.values() {
+            let (layers, bytes) = unsafe { record.footprint() };
+            estimate.layers += layers;
+            estimate.bytes += bytes;
+        }
+        estimate
+    }
+
+    /// Whether the view's window is the key window (false if not in a window)
+    pub fn is_window_key(&self, view_id: i32) -> Result<bool> {
+        let window = self.window_of(view_id)?;
+        Ok(!window.is_null() && unsafe { msg_send![window, isKeyWindow] })
+    }
+
+    /// Whether the view's window is the main window (false if not in a window)
+    pub fn is_window_main(&self, view_id: i32) -> Result<bool> {
+        let window = self.window_of(view_id)?;
+        Ok(!window.is_null() && unsafe { msg_send![window, isMainWindow] })
+    }
+
+    /// Call `on_key` with the new state whenever the view's window becomes or
+    /// resigns key
+    pub fn set_key_state_callback(&mut self, view_id: i32, on_key: Box<dyn Fn(bool)>) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let window = self.window_of(view_id)?;
+        if window.is_null() {
+            return Err(GlassError::InvalidState(
+                "view is not in a window".to_string(),
+            ));
+        }
+
+        let on_key: Rc<dyn Fn(bool)> = on_key.into();
+        let on_resign = on_key.clone();
+        let enabled = self.views[&view_id].observers_enabled.clone();
+        let resign_enabled = enabled.clone();
+        let observers = unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            vec![
+                NotificationObserver::new(
+                    center,
+                    "NSWindowDidBecomeKeyNotification",
+                    window,
+                    move || {
+                        if enabled.get() {
+                            on_key(true)
+                        }
+                    },
+                ),
+                NotificationObserver::new(
+                    center,
+                    "NSWindowDidResignKeyNotification",
+                    window,
+                    move || {
+                        if resign_enabled.get() {
+                            on_resign(false)
+                        }
+                    },
+                ),
+            ]
+        };
+
+        // Replacing the observers drops (and unregisters) any previous ones
+        self.views.get_mut(&view_id).unwrap().key_observers = observers;
+        Ok(())
+    }
+
+    /// Call `on_begin`/`on_end` when the view's window starts and ends a
+    /// live resize
+    pub fn set_live_resize_callbacks(
+        &mut self,
+        view_id: i32,
+        on_begin: Box<dyn Fn()>,
+        on_end: Box<dyn Fn()>,
+    ) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let window = self.window_of(view_id)?;
+        if window.is_null() {
+            return Err(GlassError::InvalidState(
+                "view is not in a window".to_string(),
+            ));
+        }
+
+        let enabled = self.views[&view_id].observers_enabled.clone();
+        let end_enabled = enabled.clone();
+        let observers = unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            vec![
+                NotificationObserver::new(
+                    center,
+                    "NSWindowWillStartLiveResizeNotification",
+                    window,
+                    move || {
+                        if enabled.get() {
+                            on_begin()
+                        }
+                    },
+                ),
+                NotificationObserver::new(
+                    center,
+                    "NSWindowDidEndLiveResizeNotification",
+                    window,
+                    move || {
+                        if end_enabled.get() {
+                            on_end()
+                        }
+                    },
+                ),
+            ]
+        };
+
+        self.views.get_mut(&view_id).unwrap().live_resize_observers = observers;
+        Ok(())
+    }
+
+    /// Pause or resume the view's appearance, key-state and resize observers
+    pub fn set_observers_enabled(&self, view_id: i32, enabled: bool) -> Result<()> {
+        self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .observers_enabled
+            .set(enabled);
+        Ok(())
+    }
+
+    /// Call `on_resize` whenever the view's window resizes
+    pub fn auto_track_window(&mut self, view_id: i32, on_resize: Box<dyn Fn()>) -> Result<()> {
+        unsafe { self.ensure_main_thread()? };
+
+        let window = self.window_of(view_id)?;
+        if window.is_null() {
+            return Err(GlassError::InvalidState(
+                "view is not in a window".to_string(),
+            ));
+        }
+
+        let enabled = self.views[&view_id].observers_enabled.clone();
+        let observer = unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            NotificationObserver::new(center, "NSWindowDidResizeNotification", window, move || {
+                if enabled.get() {
+                    on_resize()
+                }
+            })
+        };
+
+        self.views.get_mut(&view_id).unwrap().resize_observer = Some(observer);
+        Ok(())
+    }
+
+    /// The window a view is in, or nil
+    fn window_of(&self, view_id: i32) -> Result<id> {
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        Ok(unsafe { msg_send![view, window] })
+    }
+
+    /// List glass-related selectors of the glass view class and its superclasses
+    pub fn dump_glass_selectors(&self) -> Vec<String> {
+        const KEYWORDS: [&str; 4] = ["scrim", "variant", "subdued", "glass"];
+
+        let mut selectors = Vec::new();
+        let mut class = self.glass_class().or_else(|| Class::get("NSVisualEffectView"));
+        while let Some(current) = class {
+            if current.name() == "NSView" {
+                break;
+            }
+            for method in current.instance_methods().iter() {
+                let sel = method.name();
+                let name = sel.name();
+                let lower = name.to_lowercase();
+                if KEYWORDS.iter().any(|keyword| lower.contains(keyword)) {
+                    selectors.push(format!("{} -{}", current.name(), name));
+                }
+            }
+            class = current.superclass();
+        }
+
+        selectors.sort();
+        selectors.dedup();
+        selectors
+    }
+
+    /// Create, configure, read back and remove a view in an offscreen window
+    ///
+    /// The probe runs on a scratch manager with default policies, no
+    /// environment defaults or debug overlay and its own view IDs, sharing
+    /// only the glass class, so this manager's settings cannot skew it.
+    pub fn self_test(&self) -> SelfTestReport {
+        let mut probe = Self::new();
+        probe.glass_class = self.glass_class;
+        probe.run_self_test()
+    }
+
+    fn run_self_test(&mut self) -> SelfTestReport {
+        const TINT: &str = "#ff000080";
+        const VARIANT: GlassMaterialVariant = GlassMaterialVariant::Dock;
+
+        let mut report = SelfTestReport::default();
+        if let Err(error) = unsafe { self.ensure_main_thread() } {
+            report.failures.push(error.to_string());
+            return report;
+        }
+
+        unsafe {
+            // Borderless, far off screen and never ordered in
+            let frame = NSRect::new(NSPoint::new(-10000.0, -10000.0), NSSize::new(200.0, 200.0));
+            let window: id = msg_send![class!(NSWindow), alloc];
+            let window: id = msg_send![
+                window,
+                initWithContentRect: frame
+                styleMask: 0_usize
+                backing: 2_usize
+                defer: NO
+            ];
+            if window.is_null() {
+                report.failures.push("creation: could not create the test window".to_string());
+                return report;
+            }
+            let _: () = msg_send![window, setReleasedWhenClosed: NO];
+            let content: id = msg_send![window, contentView];
+
+            let options = GlassOptions {
+                tint_color: Some(TINT.to_string()),
+                ..Default::default()
+            };
+            match self.add_glass_view(content as *mut c_void, options) {
+                Ok(view_id) => {
+                    let record = &self.views[&view_id];
+                    let view = record.view;
+                    report.native_glass = record.backend == GlassBackend::Native;
+                    let superview: id = msg_send![view, superview];
+                    report.creation = superview == content;
+                    if !report.creation {
+                        report.failures.push("creation: view was not attached".to_string());
+                    }
+
+                    let expected = parse_hex_rgba(TINT).unwrap();
+                    report.tint = match self.resolved_tint(view_id) {
+                        Ok(Some(actual)) => {
+                            let close = |a: f64, b: f64| (a - b).abs() < 0.01;
+                            close(actual.0, expected.0)
+                                && close(actual.1, expected.1)
+                                && close(actual.2, expected.2)
+                                && close(actual.3, expected.3)
+                        }
+                        _ => false,
+                    };
+                    if !report.tint {
+                        report.failures.push("tint: color did not read back".to_string());
+                    }
+
+                    report.variant = self.check_int_roundtrip(
+                        view_id,
+                        "variant",
+                        VARIANT.raw(),
+                        |manager| manager.set_variant(view_id, VARIANT),
+                        &mut report.failures,
+                    );
+                    report.scrim = self.check_int_roundtrip(
+                        view_id,
+                        "scrimState",
+                        1,
+                        |manager| manager.set_scrim_state(view_id, 1),
+                        &mut report.failures,
+                    );
+                    report.subdued = self.check_int_roundtrip(
+                        view_id,
+                        "subduedState",
+                        1,
+                        |manager| manager.set_subdued_state(view_id, 1),
+                        &mut report.failures,
+                    );
+
+                    let _: id = msg_send![view, retain];
+                    report.removal = match self.remove_view(view_id) {
+                        Ok(()) => {
+                            let superview: id = msg_send![view, superview];
+                            superview.is_null()
+                        }
+                        Err(_) => false,
+                    };
+                    let _: () = msg_send![view, release];
+                    if !report.removal {
+                        report.failures.push("removal: view is still attached".to_string());
+                    }
+                }
+                Err(error) => report.failures.push(format!("creation: {}", error)),
+            }
+
+            let _: () = msg_send![window, close];
+            let _: () = msg_send![window, release];
+        }
+        report
+    }
+
+    /// Set an integer property with `set`, then check the view reports `expected`
+    fn check_int_roundtrip(
+        &mut self,
+        view_id: i32,
+        key: &str,
+        expected: i64,
+        set: impl FnOnce(&mut Self) -> Result<()>,
+        failures: &mut Vec<String>,
+    ) -> bool {
+        if let Err(error) = set(self) {
+            failures.push(format!("{}: {}", key, error));
+            return false;
+        }
+
+        let view = self.views[&view_id].view;
+        let actual = unsafe { int_property(view, key) };
+        if actual == Some(expected) {
+            true
+        } else {
+            failures.push(format!("{}: set {} but read back {:?}", key, expected, actual));
+            false
+        }
+    }
+
+    /// Describe the material state the native view reports, as `key=value` pairs
+    pub fn effective_material(&self, view_id: i32) -> Result<String> {
+        const KEYS: [&str; 9] = [
+            "variant",
+            "scrimState",
+            "subduedState",
+            "style",
+            "material",
+            "blendingMode",
+            "state",
+            "isEmphasized",
+            "interiorBackgroundStyle",
+        ];
+
+        let view = self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            let class: id = msg_send![view, className];
+            let mut parts = vec![ns_string(class)];
+
+            for key in KEYS {
+                let Some(sel) = self.try_get_selector(key) else {
+                    continue;
+                };
+                let responds: bool = msg_send![view, respondsToSelector: sel];
+                if !responds {
+                    continue;
+                }
+                let name = NSString::alloc(nil).init_str(key);
+                let value: id = msg_send![view, valueForKey: name];
+                let _: () = msg_send![name, release];
+                let description: id = if value.is_null() { nil } else { msg_send![value, description] };
+                parts.push(format!("{}={}", key, ns_string(description)));
+            }
+
+            let appearance: id = msg_send![view, effectiveAppearance];
+            if !appearance.is_null() {
+                let name: id = msg_send![appearance, name];
+                parts.push(format!("appearance={}", ns_string(name)));
+            }
+
+            let layer: id = msg_send![view, layer];
+            if !layer.is_null() {
+                let layer_class: id = msg_send![layer, className];
+                parts.push(format!("layer={}", ns_string(layer_class)));
+            }
+
+            Ok(parts.join(" "))
+        }
+    }
+
+    /// Find the glass view previously added to a container handle
+    ///
+    /// Separators are not glass and are skipped. If several views share the
+    /// container, the lowest ID is returned.
+    pub fn view_for_handle(&self, handle: *mut c_void) -> Option<i32> {
+        self.views
+            .iter()
+            .filter(|(_, record)| record.container == Some(handle as id) && !record.separator)
+            .map(|(&view_id, _)| view_id)
+            .min()
+    }
+
+    /// Regenerate scale-dependent masks and highlights for each view's display
+    pub fn recompute_masks(&self) {
+        for record in self.views.values() {
+            let options = &record.options;
+            unsafe {
+                if options.corner_radius > 0.0 {
+                    let is_visual_effect: bool =
+                        msg_send![record.view, isKindOfClass: class!(NSVisualEffectView)];
+                    if is_visual_effect {
+                        let mask = create_rounded_mask_image(options.corner_radius, options.round_corners);
+                        let _: () = msg_send![record.view, setMaskImage: mask];
+                    }
+                }
+
+                if let Some(ref rim) = options.rim_highlight
+                    && let Ok(color) = self.parse_hex_color(rim)
+                {
+                    apply_rim_highlight(record.view, color, options.corner_radius, options.round_corners);
+                }
+            }
+        }
+    }
+
+    /// Call `f` for every managed view, in no particular order
+    pub fn for_each_view(&self, mut f: impl FnMut(i32, ViewInfo<'_>)) {
+        for (&view_id, record) in &self.views {
+            f(view_id, record.info());
+        }
+    }
+
+    /// Remove every view matching `predicate`, returning their IDs in order
+    pub fn remove_views_where(
+        &mut self,
+        predicate: impl Fn(i32, ViewInfo<'_>) -> bool,
+    ) -> Result<Vec<i32>> {
+        let mut matching: Vec<i32> = self
+            .views
+            .iter()
+            .filter(|&(&view_id, record)| predicate(view_id, record.info()))
+            .map(|(&view_id, _)| view_id)
+            .collect();
+        matching.sort_unstable();
+
+        for &view_id in &matching {
+            self.remove_view(view_id)?;
+        }
+        Ok(matching)
+    }
+
+    /// Check whether two IDs refer to the same native view
+    pub fn same_native_view(&self, a: i32, b: i32) -> bool {
+        match (self.views.get(&a), self.views.get(&b)) {
+            (Some(a), Some(b)) => a.view == b.view,
+            _ => false,
+        }
+    }
+
+    /// Get the options a view is currently configured with
+    pub fn options_for(&self, view_id: i32) -> Result<GlassOptions> {
+        self.views.get(&view_id)
+            .map(|record| record.options.clone())
+            .ok_or(GlassError::InvalidViewId(view_id))
+    }
+
+    /// Associate caller-provided data with a view
+    pub fn set_user_data(&mut self, view_id: i32, data: i64) -> Result<()> {
+        let record = self.views.get_mut(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+        record.user_data = data;
+        Ok(())
+    }
+
+    /// Get the data associated with a view (0 if never set)
+    pub fn get_user_data(&self, view_id: i32) -> Result<i64> {
+        self.views.get(&view_id)
+            .map(|record| record.user_data)
+            .ok_or(GlassError::InvalidViewId(view_id))
+    }
+
+    /// Set integer property using runtime
+    pub fn set_int_property(&self, view_id: i32, key: &str, value: i64) -> Result<()> {
+        let number: id = unsafe { msg_send![class!(NSNumber), numberWithLongLong: value] };
+        self.set_number_property(view_id, key, number)
+    }
+
+    /// Set floating-point property using runtime
+    pub fn set_float_property(&self, view_id: i32, key: &str, value: f64) -> Result<()> {
+        let number: id = unsafe { msg_send![class!(NSNumber), numberWithDouble: value] };
+        self.set_number_property(view_id, key, number)
+    }
+
+    /// Set a property to an NSNumber via KVC, if the view has a setter for it
+    fn set_number_property(&self, view_id: i32, key: &str, number: id) -> Result<()> {
+        let view = &self.views.get(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?
+            .view;
+
+        unsafe {
+            // Try private setter first (set_key:)
+            let private_setter = format!("set_{}:", key);
+            if let Some(sel) = self.try_get_selector(&private_setter) {
+                let responds: bool = msg_send![*view, respondsToSelector: sel];
+                if responds {
+                    let _: () = msg_send![*view, setValue:number forKey: NSString::alloc(nil).init_str(key)];
+                    return Ok(());
+                }
+            }
+
+            // Try public setter (setKey:)
+            let public_setter = format!(
+                "set{}{}:",
+                key.chars().next().unwrap().to_uppercase(),
+                &key[1..]
+            );
+            if let Some(sel) = self.try_get_selector(&public_setter) {
+                let responds: bool = msg_send![*view, respondsToSelector: sel];
+                if responds {
+                    let _: () = msg_send![*view, setValue:number forKey: NSString::alloc(nil).init_str(key)];
+                    return Ok(());
+                }
+            }
+
+            Err(GlassError::RuntimeError(format!(
+                "Property '{}' not found or not accessible",
+                key
+            )))
+        }
+    }
+
+    /// Set the glass thickness through whichever private key the view has
+    pub fn set_thickness(&mut self, view_id: i32, value: f64) -> Result<()> {
+        let mut result = Ok(());
+        for key in ["thickness", "intensity"] {
+            result = self.set_float_property(view_id, key, value);
+            if !matches!(result, Err(GlassError::RuntimeError(_))) {
+                break;
+            }
+        }
+        if result.is_ok() {
+            self.views.get_mut(&view_id).unwrap().thickness = Some(value);
+        }
+        result
+    }
+
+    /// Try to get a selector
+    fn try_get_selector(&self, name: &str) -> Option<Sel> {
+        Some(Sel::register(name))
+    }
+
+    /// Remove every view added to a container handle
+    pub fn remove_view_by_handle(&mut self, handle: *mut c_void) -> Result<()> {
+        let mut removed = false;
+        while let Some(view_id) = self.view_for_handle(handle) {
+            self.remove_view(view_id)?;
+            removed = true;
+        }
+
+        if removed {
+            Ok(())
+        } else {
+            Err(GlassError::InvalidHandle)
+        }
+    }
+
+    /// Remove a glass view
+    pub fn remove_view(&mut self, view_id: i32) -> Result<()> {
+        let record = self.views.remove(&view_id)
+            .ok_or(GlassError::InvalidViewId(view_id))?;
+
+        if self.recycle_ids {
+            self.free_ids.insert(view_id);
+        }
+
+        unsafe {
+            if let Some((window, content)) = record.replaced_content {
+                // Put the original content view back in place of the glass
+                let _: () = msg_send![content, removeFromSuperview];
+                let _: () = msg_send![window, setContentView: content];
+                let _: () = msg_send![content, release];
+            } else {
+                let _: () = msg_send![record.view, removeFromSuperview];
+            }
+            if let Some(bg) = record.background {
+                let _: () = msg_send![bg, removeFromSuperview];
+            }
+            if let Some(hover) = record.hover {
+                remove_hover_tracking(record.view, hover);
+            }
+            if let Some(image_view) = record.background_image {
+                let _: () = msg_send![image_view, removeFromSuperview];
+            }
+            if let Some(shadow_view) = record.shadow_view {
+                let _: () = msg_send![shadow_view, removeFromSuperview];
+            }
+            if let Some(snapshot) = record.snapshot {
+                let _: () = msg_send![snapshot, removeFromSuperview];
+            }
+            // The scroll view draws its backgrounds again once no glass is left in it
+            if let (Some(clip_view), Some(draws_background)) =
+                (record.clip_view, record.scroll_draws_background)
+                && !self.views.values().any(|other| other.clip_view == Some(clip_view))
+                && let Some(scroll_view) = record.container
+            {
+                restore_draws_background(scroll_view, clip_view, draws_background);
+            }
+        }
+
+        // The group's glass container goes with its last segment
+        if let Some(group) = record.group
+            && !self.views.values().any(|other| other.group == Some(group))
+        {
+            unsafe {
+                let _: () = msg_send![group, removeFromSuperview];
+                let _: () = msg_send![group, release];
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Put back the `drawsBackground` of a scroll view and its clip view
+unsafe fn restore_draws_background(scroll_view: id, clip_view: id, (scroll, clip): (bool, bool)) {
+    let _: () = msg_send![scroll_view, setDrawsBackground: if scroll { YES } else { NO }];
+    let _: () = msg_send![clip_view, setDrawsBackground: if clip { YES } else { NO }];
+}
+
+/// Begin a `CATransaction` with implicit animations disabled
+pub fn begin_transaction() {
+    unsafe {
+        let _: () = msg_send![class!(CATransaction), begin];
+        let _: () = msg_send![class!(CATransaction), setDisableActions: YES];
+    }
+}
+
+/// Commit the innermost `CATransaction`
+pub fn commit_transaction() {
+    unsafe {
+        let _: () = msg_send![class!(CATransaction), commit];
+    }
+}
+
+/// Block-based `NSNotificationCenter` observer
+///
+/// The observer is removed when this is dropped.
+pub struct NotificationObserver {
+    center: id,
+    token: id,
+}
+
+impl NotificationObserver {
+    /// Call `callback` whenever `center` posts `name` for `object` (nil for any)
+    pub unsafe fn new(center: id, name: &str, object: id, callback: impl Fn() + 'static) -> Self {
+        unsafe { Self::with_sender(center, name, object, move |_sender| callback()) }
+    }
+
+    /// Like `new`, passing the object that posted the notification to `callback`
+    pub unsafe fn with_sender(
+        center: id,
+        name: &str,
+        object: id,
+        callback: impl Fn(id) + 'static,
+    ) -> Self {
+        let block = RcBlock::new(move |notification: *mut objc2::runtime::AnyObject| {
+            let sender: id = unsafe { msg_send![notification as id, object] };
+            callback(sender);
+        });
+        let block: &Block<dyn Fn(*mut objc2::runtime::AnyObject)> = &block;
+
+        let name = unsafe { NSString::alloc(nil).init_str(name) };
+        let token: id = msg_send![
+            center,
+            addObserverForName: name
+            object: object
+            queue: nil
+            usingBlock: block as *const _
+        ];
+        let _: () = msg_send![name, release];
+        let _: id = msg_send![token, retain];
+        let _: id = msg_send![center, retain];
+
+        Self { center, token }
+    }
+
+    /// Call `on_close` when the window containing `view` is about to close
+    ///
+    /// Returns `None` if the view is not in a window yet.
+    pub fn window_will_close(view: *mut c_void, on_close: impl Fn() + 'static) -> Option<Self> {
+        unsafe {
+            let window: id = msg_send![view as id, window];
+            if window.is_null() {
+                return None;
+            }
+
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            Some(Self::new(center, "NSWindowWillCloseNotification", window, on_close))
+        }
+    }
+
+    /// Call `on_move` with the native view whenever a managed view moves to a window
+    pub fn view_did_move_to_window(on_move: impl Fn(id) + 'static) -> Self {
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            Self::with_sender(center, DID_MOVE_TO_WINDOW_NOTIFICATION, nil, on_move)
+        }
+    }
+
+    /// Call `on_change` when the accessibility display options change
+    /// (Reduce Transparency, Reduce Motion, ...)
+    pub fn accessibility_display_options_changed(on_change: impl Fn() + 'static) -> Self {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let center: id = msg_send![workspace, notificationCenter];
+            Self::new(
+                center,
+                "NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification",
+                nil,
+                on_change,
+            )
+        }
+    }
+}
+
+impl Drop for NotificationObserver {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.center, removeObserver: self.token];
+            let _: () = msg_send![self.token, release];
+            let _: () = msg_send![self.center, release];
+        }
+    }
+}
+
+/// Key-value observer of a view's `effectiveAppearance`
+///
+/// The observer is removed when this is dropped.
+pub struct AppearanceObserver {
+    view: id,
+    target: id,
+    _callback: Box<Box<dyn Fn(Appearance)>>,
+}
+
+impl AppearanceObserver {
+    /// Call `on_change` with the new appearance whenever the view's
+    /// effective appearance changes
+    pub unsafe fn new(view: id, on_change: Box<dyn Fn(Appearance)>) -> Option<Self> {
+        let callback = Box::new(on_change);
+
+        let target: id = msg_send![appearance_observer_class(), new];
+        if target.is_null() {
+            return None;
+        }
+        unsafe {
+            (*target).set_ivar::<*mut c_void>(
+                APPEARANCE_CALLBACK_IVAR,
+                &*callback as *const Box<dyn Fn(Appearance)> as *mut c_void,
+            );
+        }
+
+        let observer = Self {
+            view,
+            target,
+            _callback: callback,
+        };
+        unsafe { observer.observe() };
+        Some(observer)
+    }
+
+    /// Observe another view, e.g. one that replaced the original
+    pub unsafe fn rebind(&mut self, view: id) {
+        unsafe {
+            self.unobserve();
+            self.view = view;
+            self.observe();
+        }
+    }
+
+    unsafe fn observe(&self) {
+        let key_path = unsafe { NSString::alloc(nil).init_str("effectiveAppearance") };
+        // NSKeyValueObservingOptionNew
+        let options: usize = 0x01;
+        let _: () = msg_send![
+            self.view,
+            addObserver: self.target
+            forKeyPath: key_path
+            options: options
+            context: std::ptr::null_mut::<c_void>()
+        ];
+        let _: () = msg_send![key_path, release];
+        // The observer must be removed before the view goes away
+        let _: id = msg_send![self.view, retain];
+    }
+
+    unsafe fn unobserve(&self) {
+        let key_path = unsafe { NSString::alloc(nil).init_str("effectiveAppearance") };
+        let _: () = msg_send![self.view, removeObserver: self.target forKeyPath: key_path];
+        let _: () = msg_send![key_path, release];
+        let _: () = msg_send![self.view, release];
+    }
+}
+
+impl Drop for AppearanceObserver {
+    fn drop(&mut self) {
+        unsafe {
+            self.unobserve();
+            let _: () = msg_send![self.target, release];
+        }
+    }
+}
+
+/// Put `content` inside `glass`, using its `contentView` property when it has one
+///
+/// Content assigned through NSGlassEffectView's `contentView` renders correctly
+/// above the glass; other backends get a plain autoresizing subview.
+unsafe fn embed_content(glass: id, content: id) {
+    let has_content_view: bool = msg_send![glass, respondsToSelector: sel!(setContentView:)];
+    if has_content_view {
+        let _: () = msg_send![glass, setContentView: content];
+    } else {
+        let bounds: NSRect = msg_send![glass, bounds];
+        let _: () = msg_send![content, setFrame: bounds];
+        let mask: usize = 2 | 16;
+        let _: () = msg_send![content, setAutoresizingMask: mask];
+        let _: () = msg_send![glass, addSubview: content];
+    }
+}
+
+/// Resolve a view's effective appearance to light or dark
+unsafe fn effective_appearance(view: id) -> Appearance {
+    let effective: id = msg_send![view, effectiveAppearance];
+    let names = [Appearance::Light, Appearance::Dark]
+        .map(|appearance| unsafe { NSString::alloc(nil).init_str(appearance.name()) });
+    let candidates: id = msg_send![
+        class!(NSArray),
+        arrayWithObjects: names.as_ptr()
+        count: names.len()
+    ];
+    let best: id = msg_send![effective, bestMatchFromAppearancesWithNames: candidates];
+    let is_dark: BOOL = msg_send![best, isEqualToString: names[1]];
+    for name in names {
+        let _: () = msg_send![name, release];
+    }
+
+    if is_dark == YES {
+        Appearance::Dark
+    } else {
+        Appearance::Light
+    }
+}
+
+/// Set `blendingMode` if the view supports it, returning whether it did
+unsafe fn apply_blending_mode(view: id, mode: BlendingMode) -> bool {
+    let responds: bool = msg_send![view, respondsToSelector: sel!(setBlendingMode:)];
+    if responds {
+        let _: () = msg_send![view, setBlendingMode: mode as isize];
+    }
+    responds
+}
+
+/// Swap a view's class for a runtime subclass with extra method overrides
+///
+/// Subclasses are registered once per base class and suffix. Views already
+/// carrying the suffix are left untouched, and so are views being observed
+/// with KVO, whose class must stay the one KVO installed.
+unsafe fn swizzle_view_class(view: id, suffix: &str, add_methods: impl FnOnce(&mut ClassDecl)) {
+    let current = unsafe { &*object_getClass(view) };
+
+    // Already swizzled, possibly beneath KVO's own subclass
+    let mut class = Some(current);
+    while let Some(ancestor) = class {
+        if ancestor.name().ends_with(suffix) {
+            return;
+        }
+        class = ancestor.superclass();
+    }
+    if current.name().starts_with(KVO_CLASS_PREFIX) {
+        log::warn!("Not adding {} to {}, which is observed with KVO", suffix, current.name());
+        return;
+    }
+
+    let name = format!("{}_{}", current.name(), suffix);
+    let class = match Class::get(&name) {
+        Some(class) => class,
+        None => {
+            let Some(mut decl) = ClassDecl::new(&name, current) else {
+                return;
+            };
+            add_methods(&mut decl);
+            decl.register()
+        }
+    };
+
+    unsafe { object_setClass(view, class) };
+}
+
+/// A copy of `options` with the tint greyed for the disabled look, if it has a tint
+fn disabled_tint_options(options: &GlassOptions) -> Option<GlassOptions> {
+    let rgba = parse_hex_rgba(options.tint_color.as_ref()?).ok()?;
+    Some(GlassOptions {
+        tint_color: Some(format_hex_rgba(desaturate(rgba, DISABLED_DESATURATION))),
+        ..options.clone()
+    })
+}
+
+/// Alpha multiplier for views made to look disabled
+const DISABLED_ALPHA_FACTOR: f64 = 0.5;
+/// How far the tint of a disabled view is mixed toward grey
+const DISABLED_DESATURATION: f64 = 0.8;
+
+/// Average relative luminance of what is drawn behind a view in its window
+///
+/// The view is hidden while its container renders the region into a bitmap,
+/// so only in-window content is seen, not the desktop.
+unsafe fn backdrop_luminance(view: id) -> Option<f64> {
+    let container: id = msg_send![view, superview];
+    if container.is_null() {
+        return None;
+    }
+
+    let frame: NSRect = msg_send![view, frame];
+    let was_hidden: BOOL = msg_send![view, isHidden];
+    let _: () = msg_send![view, setHidden: YES];
+    let rep: id = msg_send![container, bitmapImageRepForCachingDisplayInRect: frame];
+    if !rep.is_null() {
+        let _: () = msg_send![container, cacheDisplayInRect: frame toBitmapImageRep: rep];
+    }
+    let _: () = msg_send![view, setHidden: was_hidden];
+    if rep.is_null() {
+        return None;
+    }
+
+    let width: isize = msg_send![rep, pixelsWide];
+    let height: isize = msg_send![rep, pixelsHigh];
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+
+    // A coarse grid is plenty for an average
+    const GRID: isize = 8;
+    let srgb: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+    let mut total = 0.0;
+    let mut samples = 0.0;
+    for gy in 0..GRID {
+        for gx in 0..GRID {
+            let x = (gx * 2 + 1) * width / (GRID * 2);
+            let y = (gy * 2 + 1) * height / (GRID * 2);
+            let color: id = msg_send![rep, colorAtX: x y: y];
+            let color: id = if color.is_null() { nil } else { msg_send![color, colorUsingColorSpace: srgb] };
+            if color.is_null() {
+                continue;
+            }
+            let r: f64 = msg_send![color, redComponent];
+            let g: f64 = msg_send![color, greenComponent];
+            let b: f64 = msg_send![color, blueComponent];
+            let a: f64 = msg_send![color, alphaComponent];
+            total += (0.2126 * r + 0.7152 * g + 0.0722 * b) * a;
+            samples += 1.0;
+        }
+    }
+
+    (samples > 0.0).then(|| total / samples)
+}
+
+/// Round a view's corners, clipping its contents
+///
+/// The mask is also remembered on the view and restored after layout, since
+/// the first layout pass can replace or reset the layer and show square
+/// corners for a frame.
+unsafe fn apply_corner_radius(view: id, radius: f64, corners: CornerMask) {
+    unsafe { store_corner_mask(view, Some((radius, corners))) };
+
+    let _: () = msg_send![view, setWantsLayer: YES];
+    let layer: id = msg_send![view, layer];
+    if !layer.is_null() {
+        let _: () = msg_send![layer, setCornerRadius: radius];
+        let _: () = msg_send![layer, setMaskedCorners: corners.bits() as usize];
+        let _: () = msg_send![layer, setMasksToBounds: YES];
+    }
+
+    // NSVisualEffectView does not clip its material to the layer's
+    // corners, so give the fallback a matching mask image
+    let is_visual_effect: bool = msg_send![view, isKindOfClass: class!(NSVisualEffectView)];
+    if is_visual_effect {
+        let mask = unsafe { create_rounded_mask_image(radius, corners) };
+        let _: () = msg_send![view, setMaskImage: mask];
+    }
+}
+
+/// Round the opaque background like the glass unless told otherwise
+unsafe fn round_background(background: id, options: &GlassOptions) {
+    let layer: id = msg_send![background, layer];
+    if layer.is_null() {
+        return;
+    }
+
+    let radius = options.background_corner_radius.unwrap_or(options.corner_radius);
+    let _: () = msg_send![layer, setCornerRadius: radius];
+    let _: () = msg_send![layer, setMaskedCorners: options.round_corners.bits() as usize];
+    let _: () = msg_send![layer, setMasksToBounds: if radius > 0.0 { YES } else { NO }];
+}
+
+/// Undo a tint set by `apply_tint`
+unsafe fn clear_tint(view: id) {
+    let responds: bool = msg_send![view, respondsToSelector: sel!(setTintColor:)];
+    let fills: bool = msg_send![view, respondsToSelector: sel!(setFillColor:)];
+    if responds {
+        let _: () = msg_send![view, setTintColor: nil];
+    } else if fills {
+        // Solid color views go back to their untinted fill
+        let color: id = msg_send![class!(NSColor), windowBackgroundColor];
+        let _: () = msg_send![view, setFillColor: color];
+    } else {
+        let layer: id = msg_send![view, layer];
+        if !layer.is_null() {
+            let _: () = msg_send![layer, setBackgroundColor: std::ptr::null::<c_void>()];
+        }
+    }
+}
+
+static CORNER_RADIUS_KEY: u8 = 0;
+static MASKED_CORNERS_KEY: u8 = 0;
+static FOCUSABLE_KEY: u8 = 0;
+static DEFAULT_WANTS_LAYER_KEY: u8 = 0;
+static VIBRANCY_KEY: u8 = 0;
+
+/// Remember a flag read by the view's class hooks, or forget it
+unsafe fn store_flag(view: id, key: &'static u8, value: Option<bool>) {
+    let number: id = match value {
+        Some(value) => msg_send![class!(NSNumber), numberWithBool: if value { YES } else { NO }],
+        None => nil,
+    };
+    unsafe {
+        objc_setAssociatedObject(view, key as *const u8 as *const c_void, number, ASSOCIATION_RETAIN_NONATOMIC);
+    }
+}
+
+/// A flag stored by `store_flag`
+unsafe fn stored_flag(view: id, key: &'static u8) -> Option<bool> {
+    let number = unsafe { objc_getAssociatedObject(view, key as *const u8 as *const c_void) };
+    if number.is_null() {
+        return None;
+    }
+    let value: BOOL = msg_send![number, boolValue];
+    Some(value == YES)
+}
+
+/// Remember the corner mask `restore_corner_mask` keeps on a view, or forget it
+unsafe fn store_corner_mask(view: id, mask: Option<(f64, CornerMask)>) {
+    let (radius, corners): (id, id) = match mask {
+        Some((radius, corners)) => (
+            msg_send![class!(NSNumber), numberWithDouble: radius],
+            msg_send![class!(NSNumber), numberWithUnsignedInteger: corners.bits() as usize],
+        ),
+        None => (nil, nil),
+    };
+
+    unsafe {
+        let key = &CORNER_RADIUS_KEY as *const u8 as *const c_void;
+        objc_setAssociatedObject(view, key, radius, ASSOCIATION_RETAIN_NONATOMIC);
+        let key = &MASKED_CORNERS_KEY as *const u8 as *const c_void;
+        objc_setAssociatedObject(view, key, corners, ASSOCIATION_RETAIN_NONATOMIC);
+    }
+}
+
+/// Put back a remembered corner mask that the view's layer has lost
+unsafe fn restore_corner_mask(view: id) {
+    let (radius, corners) = unsafe {
+        (
+            objc_getAssociatedObject(view, &CORNER_RADIUS_KEY as *const u8 as *const c_void),
+            objc_getAssociatedObject(view, &MASKED_CORNERS_KEY as *const u8 as *const c_void),
+        )
+    };
+    let layer: id = msg_send![view, layer];
+    if radius.is_null() || corners.is_null() || layer.is_null() {
+        return;
+    }
+
+    let radius: f64 = msg_send![radius, doubleValue];
+    let corners: usize = msg_send![corners, unsignedIntegerValue];
+    let current_radius: f64 = msg_send![layer, cornerRadius];
+    let current_corners: usize = msg_send![layer, maskedCorners];
+    let clips: bool = msg_send![layer, masksToBounds];
+    if current_radius != radius || current_corners != corners || !clips {
+        let _: () = msg_send![layer, setCornerRadius: radius];
+        let _: () = msg_send![layer, setMaskedCorners: corners];
+        let _: () = msg_send![layer, setMasksToBounds: YES];
+    }
+}
+
+/// Constrain a view's edges to its container according to `pin`
+unsafe fn pin_to_container(view: id, container: id, pin: PinOptions) {
+    let _: () = msg_send![view, setTranslatesAutoresizingMaskIntoConstraints: NO];
+
+    // Insets on the trailing and bottom edges are measured from the container inward
+    let edges = [
+        (pin.top, sel!(topAnchor), false),
+        (pin.leading, sel!(leadingAnchor), false),
+        (pin.trailing, sel!(trailingAnchor), true),
+        (pin.bottom, sel!(bottomAnchor), true),
+    ];
+    for (inset, anchor, from_container) in edges {
+        let Some(inset) = inset else { continue };
+        let view_anchor: id = msg_send![view, performSelector: anchor];
+        let container_anchor: id = msg_send![container, performSelector: anchor];
+        let (first, second) = if from_container {
+            (container_anchor, view_anchor)
+        } else {
+            (view_anchor, container_anchor)
+        };
+        let constraint: id = msg_send![first, constraintEqualToAnchor: second constant: inset];
+        let _: () = msg_send![constraint, setActive: YES];
+    }
+}
+
+/// Keep a view's width at `ratio` times its height
+unsafe fn constrain_aspect_ratio(view: id, ratio: f64) {
+    let width: id = msg_send![view, widthAnchor];
+    let height: id = msg_send![view, heightAnchor];
+    let constraint: id = msg_send![width, constraintEqualToAnchor: height multiplier: ratio];
+    let _: () = msg_send![constraint, setActive: YES];
+}
+
+/// Configure a layer to cast a shadow
+unsafe fn apply_shadow(layer: id, shadow: Shadow) {
+    if layer.is_null() {
+        return;
+    }
+    let color: id = msg_send![class!(NSColor), blackColor];
+    let cg_color: id = msg_send![color, CGColor];
+    let _: () = msg_send![layer, setShadowColor: cg_color];
+    let _: () = msg_send![layer, setShadowOpacity: shadow.opacity as f32];
+    let _: () = msg_send![layer, setShadowRadius: shadow.radius];
+    let _: () = msg_send![layer, setShadowOffset: NSSize::new(shadow.offset_x, shadow.offset_y)];
+    let _: () = msg_send![layer, setMasksToBounds: NO];
+}
+
+/// Match a shadow view's shadow to a rounded rectangle filling its bounds
+///
+/// The path rounds all four corners; `round_corners` only affects the glass.
+unsafe fn update_shadow_path(shadow_view: id, corner_radius: f64) {
+    let layer: id = msg_send![shadow_view, layer];
+    if layer.is_null() {
+        return;
+    }
+    let bounds: NSRect = msg_send![shadow_view, bounds];
+    let radius = corner_radius
+        .min(bounds.size.width / 2.0)
+        .min(bounds.size.height / 2.0)
+        .max(0.0);
+    unsafe {
+        let path = CGPathCreateWithRoundedRect(bounds, radius, radius, std::ptr::null());
+        let _: () = msg_send![layer, setShadowPath: path];
+        CGPathRelease(path);
+    }
+}
+
+const HOOKS_SUFFIX: &str = "LiquidGlassHooks";
+const KVO_CLASS_PREFIX: &str = "NSKVONotifying_";
+const DID_MOVE_TO_WINDOW_NOTIFICATION: &str = "LiquidGlassViewDidMoveToWindowNotification";
+
+/// Give a view the class overrides every managed view relies on
+///
+/// Focus, vibrancy and the corner mask are read from the view's associated
+/// objects, so they can change later without another swizzle.
+unsafe fn install_glass_hooks(view: id) {
+    unsafe {
+        swizzle_view_class(view, HOOKS_SUFFIX, |decl| {
+            let focusable = glass_accepts_first_responder as extern "C" fn(&Object, Sel) -> BOOL;
+            decl.add_method(sel!(acceptsFirstResponder), focusable);
+            decl.add_method(sel!(canBecomeKeyView), focusable);
+            decl.add_method(
+                sel!(allowsVibrancy),
+                glass_allows_vibrancy as extern "C" fn(&Object, Sel) -> BOOL,
+            );
+            // Announce window changes so the manager can reapply the options
+            decl.add_method(
+                sel!(viewDidMoveToWindow),
+                glass_did_move_to_window as extern "C" fn(&Object, Sel),
+            );
+            decl.add_method(sel!(layout), glass_layout as extern "C" fn(&Object, Sel));
+        });
+    }
+}
+
+/// The class a `swizzle_view_class` subclass with `suffix` was derived from
+///
+/// Overrides forward to this so the swizzled class's implementation runs.
+fn swizzled_superclass<'a>(this: &'a Object, suffix: &str) -> Option<&'a Class> {
+    let mut class = Some(this.class());
+    while let Some(current) = class
+        && !current.name().ends_with(suffix)
+    {
+        class = current.superclass();
+    }
+    class.and_then(|class| class.superclass())
+}
+
+extern "C" fn glass_did_move_to_window(this: &Object, _cmd: Sel) {
+    unsafe {
+        if let Some(superclass) = swizzled_superclass(this, HOOKS_SUFFIX) {
+            let _: () = msg_send![super(this, superclass), viewDidMoveToWindow];
+        }
+
+        let window: id = msg_send![this, window];
+        if !window.is_null() {
+            restore_corner_mask(this as *const Object as id);
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let name = NSString::alloc(nil).init_str(DID_MOVE_TO_WINDOW_NOTIFICATION);
+            let _: () = msg_send![center, postNotificationName: name object: this];
+            let _: () = msg_send![name, release];
+        }
+    }
+}
+
+extern "C" fn glass_layout(this: &Object, _cmd: Sel) {
+    unsafe {
+        if let Some(superclass) = swizzled_superclass(this, HOOKS_SUFFIX) {
+            let _: () = msg_send![super(this, superclass), layout];
+        }
+        restore_corner_mask(this as *const Object as id);
+    }
+}
+
+extern "C" fn glass_accepts_first_responder(this: &Object, _cmd: Sel) -> BOOL {
+    match unsafe { stored_flag(this as *const Object as id, &FOCUSABLE_KEY) } {
+        Some(true) => YES,
+        _ => NO,
+    }
+}
+
+extern "C" fn glass_allows_vibrancy(this: &Object, _cmd: Sel) -> BOOL {
+    unsafe {
+        match stored_flag(this as *const Object as id, &VIBRANCY_KEY) {
+            Some(allows_vibrancy) => if allows_vibrancy { YES } else { NO },
+            None => match swizzled_superclass(this, HOOKS_SUFFIX) {
+                Some(superclass) => msg_send![super(this, superclass), allowsVibrancy],
+                None => NO,
+            },
+        }
+    }
+}
+
+const RIM_LAYER_NAME: &str = "LiquidGlassRim";
+
+const TINT_LAYER_NAME: &str = "LiquidGlassTint";
+const GRAIN_LAYER_NAME: &str = "LiquidGlassGrain";
+
+/// Sublayers of a view's layer with the given name, bottom to top
+unsafe fn named_sublayers(view: id, layer_name: &str) -> Vec<id> {
+    let layer: id = msg_send![view, layer];
+    if layer.is_null() {
+        return Vec::new();
+    }
+    let sublayers: id = msg_send![layer, sublayers];
+    if sublayers.is_null() {
+        return Vec::new();
+    }
+    let count: usize = msg_send![sublayers, count];
+    (0..count).map(|i| -> id { msg_send![sublayers, objectAtIndex: i] }).filter(|&sublayer| {
+        let name: id = msg_send![sublayer, name];
+        !name.is_null() && unsafe { std::ffi::CStr::from_ptr(name.UTF8String()) }
+            .to_str()
+            .is_ok_and(|name| name == layer_name)
+    }).collect()
+}
+
+/// Copy an NSString into a Rust string ("nil" for nil)
+unsafe fn ns_string(string: id) -> String {
+    if string.is_null() {
+        return "nil".to_string();
+    }
+    unsafe { std::ffi::CStr::from_ptr(string.UTF8String()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Find the rim highlight sublayer of a view's layer, if any
+unsafe fn rim_layer(view: id) -> Option<id> {
+    unsafe { named_sublayers(view, RIM_LAYER_NAME) }.into_iter().next()
+}
+
+/// Read an integer property via KVC, if the view has a getter for it
+unsafe fn int_property(view: id, key: &str) -> Option<i64> {
+    let sel = Sel::register(key);
+    let responds: bool = msg_send![view, respondsToSelector: sel];
+    if !responds {
+        return None;
+    }
+
+    let name = unsafe { NSString::alloc(nil).init_str(key) };
+    let value: id = msg_send![view, valueForKey: name];
+    let _: () = msg_send![name, release];
+    if value.is_null() {
+        return None;
+    }
+    Some(msg_send![value, longLongValue])
+}
+
+/// Bytes per pixel of an 8-bit RGBA backing store
+const BYTES_PER_PIXEL: u64 = 4;
+
+/// Backing scale of a view's window, or of the main screen if it has none
+unsafe fn backing_scale(view: id) -> f64 {
+    let window: id = msg_send![view, window];
+    if window.is_null() {
+        let screen: id = msg_send![class!(NSScreen), mainScreen];
+        if screen.is_null() { 1.0 } else { msg_send![screen, backingScaleFactor] }
+    } else {
+        msg_send![window, backingScaleFactor]
+    }
+}
+
+const DEBUG_LAYER_NAME: &str = "LiquidGlassDebug";
+
+/// Outline a view in a color derived from its ID and label its top-left corner
+unsafe fn add_debug_overlay(view: id, label: &str, view_id: i32) {
+    let _: () = msg_send![view, setWantsLayer: YES];
+    let layer: id = msg_send![view, layer];
+    if layer.is_null() {
+        return;
+    }
+
+    // Golden-ratio hue steps keep neighbouring IDs visually distinct
+    let hue = (view_id as f64 * 0.618_034).fract();
+    let color: id = msg_send![class!(NSColor),
+        colorWithHue: hue saturation: 0.9_f64 brightness: 1.0_f64 alpha: 1.0_f64];
+    let cg_color: id = msg_send![color, CGColor];
+
+    let scale = unsafe { backing_scale(view) };
+
+    let bounds: NSRect = msg_send![view, bounds];
+    let overlay: id = msg_send![class!(CALayer), layer];
+    let name = unsafe { NSString::alloc(nil).init_str(DEBUG_LAYER_NAME) };
+    let _: () = msg_send![overlay, setName: name];
+    let _: () = msg_send![name, release];
+    let _: () = msg_send![overlay, setFrame: bounds];
+    let _: () = msg_send![overlay, setBorderColor: cg_color];
+    let _: () = msg_send![overlay, setBorderWidth: 1.0 / scale.max(1.0)];
+    let _: () = msg_send![overlay, setZPosition: f64::from(f32::MAX)];
+    // kCALayerWidthSizable | kCALayerHeightSizable
+    let mask: u32 = 2 | 16;
+    let _: () = msg_send![overlay, setAutoresizingMask: mask];
+
+    let text: id = msg_send![class!(CATextLayer), layer];
+    let string = unsafe { NSString::alloc(nil).init_str(label) };
+    let _: () = msg_send![text, setString: string];
+    let _: () = msg_send![string, release];
+    let _: () = msg_send![text, setFontSize: 11.0_f64];
+    let _: () = msg_send![text, setForegroundColor: cg_color];
+    let _: () = msg_send![text, setContentsScale: scale];
+    // Layer-backed views are not flipped: the label sits at the top
+    let label_height = 16.0;
+    let label_frame = NSRect::new(
+        NSPoint::new(4.0, (bounds.size.height - label_height - 2.0).max(0.0)),
+        NSSize::new((bounds.size.width - 8.0).max(0.0), label_height),
+    );
+    let _: () = msg_send![text, setFrame: label_frame];
+    // kCALayerWidthSizable | kCALayerMinYMargin
+    let mask: u32 = 2 | 8;
+    let _: () = msg_send![text, setAutoresizingMask: mask];
+    let _: () = msg_send![overlay, addSublayer: text];
+
+    let _: () = msg_send![layer, addSublayer: overlay];
+}
+
+/// Replace a view's grain layer according to `grain_intensity`
+///
+/// The grain sits above the tint layers and below the rim highlight,
+/// clipped to the view's rounded corners.
+unsafe fn apply_grain(view: id, options: &GlassOptions) {
+    for old in unsafe { named_sublayers(view, GRAIN_LAYER_NAME) } {
+        let _: () = msg_send![old, removeFromSuperlayer];
+    }
+    let Some(intensity) = options.grain_intensity.filter(|&intensity| intensity > 0.0) else {
+        return;
+    };
+
+    let _: () = msg_send![view, setWantsLayer: YES];
+    let layer: id = msg_send![view, layer];
+    if layer.is_null() {
+        return;
+    }
+
+    let grain: id = msg_send![class!(CALayer), layer];
+    let name = unsafe { NSString::alloc(nil).init_str(GRAIN_LAYER_NAME) };
+    let _: () = msg_send![grain, setName: name];
+    let _: () = msg_send![name, release];
+
+    let pattern: id = msg_send![unsafe { grain_pattern_color() }, CGColor];
+    let _: () = msg_send![grain, setBackgroundColor: pattern];
+    let _: () = msg_send![grain, setOpacity: intensity as f32];
+    // Overlay keeps the grain from greying out the glass
+    let filter = unsafe { NSString::alloc(nil).init_str("overlayBlendMode") };
+    let _: () = msg_send![grain, setCompositingFilter: filter];
+    let _: () = msg_send![filter, release];
+    let _: () = msg_send![grain, setCornerRadius: options.corner_radius];
+    let _: () = msg_send![grain, setMaskedCorners: options.round_corners.bits() as usize];
+    let _: () = msg_send![grain, setMasksToBounds: YES];
+    // kCALayerWidthSizable | kCALayerHeightSizable
+    let resizing: u32 = 2 | 16;
+    let _: () = msg_send![grain, setAutoresizingMask: resizing];
+
+    match unsafe { rim_layer(view) } {
+        Some(rim) => {
+            let _: () = msg_send![layer, insertSublayer: grain below: rim];
+        }
+        None => {
+            let _: () = msg_send![layer, addSublayer: grain];
+        }
+    }
+
+    unsafe { layout_overlay_layers(view) };
+}
+
+/// A view's tint and grain layers, bottom to top
+unsafe fn overlay_layers(view: id) -> Vec<id> {
+    let mut layers = unsafe { named_sublayers(view, TINT_LAYER_NAME) };
+    layers.extend(unsafe { named_sublayers(view, GRAIN_LAYER_NAME) });
+    layers
+}
+
+/// Resize a view's tint and grain layers to its bounds
+unsafe fn layout_overlay_layers(view: id) {
+    let bounds: NSRect = msg_send![view, bounds];
+    for overlay in unsafe { overlay_layers(view) } {
+        let _: () = msg_send![overlay, setFrame: bounds];
+    }
+}
+
+/// Tiling pattern of faint monochrome noise, generated on first use
+unsafe fn grain_pattern_color() -> id {
+    static PATTERN: OnceLock<usize> = OnceLock::new();
+
+    *PATTERN.get_or_init(|| unsafe {
+        const PIXELS: usize = 128;
+
+        let space = NSString::alloc(nil).init_str("NSDeviceWhiteColorSpace");
+        let rep: id = msg_send![class!(NSBitmapImageRep), alloc];
+        let rep: id = msg_send![
+            rep,
+            initWithBitmapDataPlanes: std::ptr::null_mut::<*mut u8>()
+            pixelsWide: PIXELS as isize
+            pixelsHigh: PIXELS as isize
+            bitsPerSample: 8_isize
+            samplesPerPixel: 1_isize
+            hasAlpha: NO
+            isPlanar: NO
+            colorSpaceName: space
+            bytesPerRow: PIXELS as isize
+            bitsPerPixel: 8_isize
+        ];
+        let _: () = msg_send![space, release];
+
+        // xorshift32: deterministic, and random enough for a texture
+        let data: *mut u8 = msg_send![rep, bitmapData];
+        let mut state: u32 = 0x9e37_79b9;
+        for i in 0..PIXELS * PIXELS {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            *data.add(i) = (state >> 24) as u8;
+        }
+
+        // Half as many points as pixels keeps the grain crisp on Retina
+        let size = NSSize::new(PIXELS as f64 / 2.0, PIXELS as f64 / 2.0);
+        let image: id = msg_send![class!(NSImage), alloc];
+        let image: id = msg_send![image, initWithSize: size];
+        let _: () = msg_send![image, addRepresentation: rep];
+        let _: () = msg_send![rep, release];
+
+        let color: id = msg_send![class!(NSColor), colorWithPatternImage: image];
+        let _: id = msg_send![color, retain];
+        let _: () = msg_send![image, release];
+        color as usize
+    }) as id
+}
+
+/// Add (or update) a 1px inner highlight along the top edge of a view
+///
+/// The highlight is a border layer following the corner radius, faded out
+/// below the top corners by a gradient mask.
+unsafe fn apply_rim_highlight(view: id, color: id, corner_radius: f64, corners: CornerMask) {
+    let _: () = msg_send![view, setWantsLayer: YES];
+    let layer: id = msg_send![view, layer];
+    if layer.is_null() {
+        return;
+    }
+
+    let rim = match unsafe { rim_layer(view) } {
+        Some(rim) => rim,
+        None => {
+            let rim: id = msg_send![class!(CALayer), layer];
+            let name = unsafe { NSString::alloc(nil).init_str(RIM_LAYER_NAME) };
+            let _: () = msg_send![rim, setName: name];
+            let _: () = msg_send![name, release];
+
+            let mask: id = msg_send![class!(CAGradientLayer), layer];
+            let opaque: id = msg_send![class!(NSColor), whiteColor];
+            let clear: id = msg_send![class!(NSColor), clearColor];
+            let opaque: id = msg_send![opaque, CGColor];
+            let clear: id = msg_send![clear, CGColor];
+            let colors = [opaque, clear];
+            let colors: id = msg_send![class!(NSArray), arrayWithObjects: colors.as_ptr() count: 2_usize];
+            let _: () = msg_send![mask, setColors: colors];
+            // Layer-backed views are not flipped: y = 1 is the top edge
+            let _: () = msg_send![mask, setStartPoint: NSPoint::new(0.5, 1.0)];
+            let _: () = msg_send![mask, setEndPoint: NSPoint::new(0.5, 0.0)];
+            let _: () = msg_send![rim, setMask: mask];
+            // Follow the view through resizes that skip `sync_bounds`
+            // (kCALayerWidthSizable | kCALayerHeightSizable)
+            let resizing: u32 = 2 | 16;
+            let _: () = msg_send![rim, setAutoresizingMask: resizing];
+            let _: () = msg_send![mask, setAutoresizingMask: resizing];
+
+            let _: () = msg_send![layer, addSublayer: rim];
+            rim
+        }
+    };
+
+    let scale = unsafe { backing_scale(view) };
+
+    let cg_color: id = msg_send![color, CGColor];
+    let _: () = msg_send![rim, setBorderColor: cg_color];
+    let _: () = msg_send![rim, setBorderWidth: 1.0 / scale.max(1.0)];
+    let _: () = msg_send![rim, setCornerRadius: corner_radius.max(0.0)];
+    let _: () = msg_send![rim, setMaskedCorners: corners.bits() as usize];
+
+    unsafe { layout_rim_highlight(view) };
+}
+
+/// Resize a view's rim highlight to its bounds
+unsafe fn layout_rim_highlight(view: id) {
+    let Some(rim) = (unsafe { rim_layer(view) }) else {
+        return;
+    };
+
+    let bounds: NSRect = msg_send![view, bounds];
+    let _: () = msg_send![rim, setFrame: bounds];
+
+    let mask: id = msg_send![rim, mask];
+    if !mask.is_null() {
+        let _: () = msg_send![mask, setFrame: bounds];
+        // Fade out just below the top corners
+        let radius: f64 = msg_send![rim, cornerRadius];
+        let fade = if bounds.size.height > 0.0 {
+            ((radius + 4.0) / bounds.size.height).min(1.0)
+        } else {
+            1.0
+        };
+        let start: id = msg_send![class!(NSNumber), numberWithDouble: 0.0_f64];
+        let end: id = msg_send![class!(NSNumber), numberWithDouble: fade];
+        let locations = [start, end];
+        let locations: id = msg_send![class!(NSArray), arrayWithObjects: locations.as_ptr() count: 2_usize];
+        let _: () = msg_send![mask, setLocations: locations];
+    }
+}
+
+/// Build a 9-part stretchable rounded-rect image for `NSVisualEffectView` masking
+unsafe fn create_rounded_mask_image(radius: f64, corners: CornerMask) -> id {
+    let edge = radius * 2.0 + 1.0;
+    let size = NSSize::new(edge, edge);
+
+    let handler = RcBlock::new(move |rect: objc2_foundation::NSRect| -> objc2::runtime::Bool {
+        let rect = NSRect::new(
+            NSPoint::new(rect.origin.x, rect.origin.y),
+            NSSize::new(rect.size.width, rect.size.height),
+        );
+        unsafe {
+            let black: id = msg_send![class!(NSColor), blackColor];
+            let _: () = msg_send![black, set];
+            let path: id = msg_send![
+                class!(NSBezierPath),
+                bezierPathWithRoundedRect: rect
+                xRadius: radius
+                yRadius: radius
+            ];
+            let _: () = msg_send![path, fill];
+
+            // Square off the corners that should not be rounded
+            let square = NSSize::new(radius + 1.0, radius + 1.0);
+            let far = edge - square.width;
+            for (corner, origin) in [
+                (CornerMask::BOTTOM_LEFT, NSPoint::new(0.0, 0.0)),
+                (CornerMask::BOTTOM_RIGHT, NSPoint::new(far, 0.0)),
+                (CornerMask::TOP_LEFT, NSPoint::new(0.0, far)),
+                (CornerMask::TOP_RIGHT, NSPoint::new(far, far)),
+            ] {
+                if !corners.contains(corner) {
+                    let _: () = msg_send![
+                        class!(NSBezierPath),
+                        fillRect: NSRect::new(origin, square)
+                    ];
+                }
+            }
+        }
+        objc2::runtime::Bool::YES
+    });
+    let handler: &Block<dyn Fn(objc2_foundation::NSRect) -> objc2::runtime::Bool> = &handler;
+
+    let image: id = msg_send![
+        class!(NSImage),
+        imageWithSize: size
+        flipped: NO
+        drawingHandler: handler as *const _
+    ];
+    let insets = objc2_foundation::NSEdgeInsets {
+        top: radius,
+        left: radius,
+        bottom: radius,
+        right: radius,
+    };
+    let _: () = msg_send![image, setCapInsets: insets];
+    // NSImageResizingModeStretch
+    let _: () = msg_send![image, setResizingMode: 1_isize];
+
+    image
+}
+
+/// Detach a tracking area from its view and release its target
+unsafe fn remove_hover_tracking(view: id, hover: HoverTracking) {
+    let _: () = msg_send![view, removeTrackingArea: hover.area];
+    let _: () = msg_send![hover.area, release];
+    let _: () = msg_send![hover.target, release];
 }
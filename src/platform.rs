@@ -3,11 +3,21 @@
 //! This module provides a cross-platform interface that delegates to
 //! platform-specific implementations or provides no-op fallbacks.
 
+use crate::diagnostics::SelfTestReport;
 use crate::error::{GlassError, Result};
 use std::sync::{Arc, Mutex};
 
 /// Glass material variants (based on macOS private API)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// With the `variant-as-int` feature, serde uses the raw integer value
+/// instead of the name. Values without a named variant deserialize as
+/// `Unknown`, so they round-trip. Variants compare and hash by raw value, so
+/// `Unknown(2)` equals `Dock`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "variant-as-int")),
+    derive(serde::Serialize, serde::Deserialize)
+)]
 #[repr(i64)]
 pub enum GlassMaterialVariant {
     /// Regular glass effect
@@ -58,29 +68,711 @@ pub enum GlassMaterialVariant {
     Camera = 22,
     /// Cartouche popover glass
     CartouchePopover = 23,
+    /// A raw value with no named variant, e.g. one added by a newer macOS
+    Unknown(i64),
+}
+
+impl GlassMaterialVariant {
+    /// Every variant, in raw value order
+    pub fn all() -> &'static [GlassMaterialVariant] {
+        const ALL: [GlassMaterialVariant; 24] = [
+            GlassMaterialVariant::Regular,
+            GlassMaterialVariant::Clear,
+            GlassMaterialVariant::Dock,
+            GlassMaterialVariant::AppIcons,
+            GlassMaterialVariant::Widgets,
+            GlassMaterialVariant::Text,
+            GlassMaterialVariant::AVPlayer,
+            GlassMaterialVariant::FaceTime,
+            GlassMaterialVariant::ControlCenter,
+            GlassMaterialVariant::NotificationCenter,
+            GlassMaterialVariant::Monogram,
+            GlassMaterialVariant::Bubbles,
+            GlassMaterialVariant::Identity,
+            GlassMaterialVariant::FocusBorder,
+            GlassMaterialVariant::FocusPlatter,
+            GlassMaterialVariant::Keyboard,
+            GlassMaterialVariant::Sidebar,
+            GlassMaterialVariant::AbuttedSidebar,
+            GlassMaterialVariant::Inspector,
+            GlassMaterialVariant::Control,
+            GlassMaterialVariant::Loupe,
+            GlassMaterialVariant::Slider,
+            GlassMaterialVariant::Camera,
+            GlassMaterialVariant::CartouchePopover,
+        ];
+        &ALL
+    }
+
+    /// The variant with a raw value, or None if it is not a known variant
+    pub fn from_raw(value: i64) -> Option<Self> {
+        Self::all().iter().copied().find(|&variant| variant.raw() == value)
+    }
+
+    /// The raw value passed to the private `variant` setter
+    pub fn raw(self) -> i64 {
+        match self {
+            Self::Regular => 0,
+            Self::Clear => 1,
+            Self::Dock => 2,
+            Self::AppIcons => 3,
+            Self::Widgets => 4,
+            Self::Text => 5,
+            Self::AVPlayer => 6,
+            Self::FaceTime => 7,
+            Self::ControlCenter => 8,
+            Self::NotificationCenter => 9,
+            Self::Monogram => 10,
+            Self::Bubbles => 11,
+            Self::Identity => 12,
+            Self::FocusBorder => 13,
+            Self::FocusPlatter => 14,
+            Self::Keyboard => 15,
+            Self::Sidebar => 16,
+            Self::AbuttedSidebar => 17,
+            Self::Inspector => 18,
+            Self::Control => 19,
+            Self::Loupe => 20,
+            Self::Slider => 21,
+            Self::Camera => 22,
+            Self::CartouchePopover => 23,
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl PartialEq for GlassMaterialVariant {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw() == other.raw()
+    }
+}
+
+impl Eq for GlassMaterialVariant {}
+
+impl std::hash::Hash for GlassMaterialVariant {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.raw().hash(state);
+    }
+}
+
+#[cfg(feature = "variant-as-int")]
+impl serde::Serialize for GlassMaterialVariant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.raw())
+    }
+}
+
+#[cfg(feature = "variant-as-int")]
+impl<'de> serde::Deserialize<'de> for GlassMaterialVariant {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = i64::deserialize(deserializer)?;
+        Ok(GlassMaterialVariant::from_raw(value).unwrap_or(GlassMaterialVariant::Unknown(value)))
+    }
+}
+
+/// Backend used to render a glass view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GlassBackend {
+    /// NSGlassEffectView (macOS 26+)
+    Native,
+    /// NSVisualEffectView fallback
+    Fallback,
+    /// Opaque NSBox filled with the tint (or window background) color
+    SolidColor,
+    /// Plain Gaussian blur of the backdrop with no material, using the
+    /// private `CABackdropLayer`. Blur radius comes from `GlassOptions::blur_radius`.
+    BlurOnly,
+}
+
+/// Borrowed view of a managed view's bookkeeping, passed to `for_each_view`
+/// and `remove_views_where`
+#[derive(Debug, Clone, Copy)]
+pub struct ViewInfo<'a> {
+    /// Backend the view currently uses
+    pub backend: GlassBackend,
+    /// Options the view was configured with
+    pub options: &'a GlassOptions,
+    /// Last material variant set
+    pub variant: Option<GlassMaterialVariant>,
+    /// Caller-provided data associated with the view
+    pub user_data: i64,
+    /// Handle the view was added to (NSView* on macOS), or null if detached
+    pub container: *mut std::ffi::c_void,
+}
+
+/// Restorable state of a single view
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ViewState {
+    /// View ID at the time the state was captured
+    pub id: i32,
+    /// Options the view was configured with
+    pub options: GlassOptions,
+    /// Last material variant set, if any
+    pub variant: Option<GlassMaterialVariant>,
+    /// Last scrim state set, if any
+    pub scrim_state: Option<i64>,
+    /// Last subdued state set, if any
+    pub subdued_state: Option<i64>,
+}
+
+/// Rectangle in the container's coordinate space, in points
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    /// X origin
+    pub x: f64,
+    /// Y origin
+    pub y: f64,
+    /// Width
+    pub width: f64,
+    /// Height
+    pub height: f64,
+}
+
+impl Rect {
+    /// Create a rectangle from its origin and size
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self { x, y, width, height }
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl From<Rect> for cocoa::foundation::NSRect {
+    fn from(rect: Rect) -> Self {
+        cocoa::foundation::NSRect::new(
+            cocoa::foundation::NSPoint::new(rect.x, rect.y),
+            cocoa::foundation::NSSize::new(rect.width, rect.height),
+        )
+    }
+}
+
+/// What a glass view blurs (mirrors `NSVisualEffectBlendingMode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(isize)]
+pub enum BlendingMode {
+    /// Blur the content behind the window (the desktop)
+    BehindWindow = 0,
+    /// Blur content within the window, behind the view
+    WithinWindow = 1,
+}
+
+/// How accessibility display settings are honored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AccessibilityPolicy {
+    /// Use the solid color backend while "Reduce Transparency" is on
+    pub honor_reduce_transparency: bool,
+    /// Make animated changes instant while "Reduce Motion" is on
+    pub honor_reduce_motion: bool,
+}
+
+impl AccessibilityPolicy {
+    /// Policy honoring every supported setting
+    pub fn honor_all() -> Self {
+        Self {
+            honor_reduce_transparency: true,
+            honor_reduce_motion: true,
+        }
+    }
+}
+
+/// System appearance a view can be pinned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Appearance {
+    /// Light appearance (NSAppearanceNameAqua)
+    Light,
+    /// Dark appearance (NSAppearanceNameDarkAqua)
+    Dark,
+}
+
+impl Appearance {
+    /// The `NSAppearanceName` for this appearance
+    pub fn name(self) -> &'static str {
+        match self {
+            Appearance::Light => "NSAppearanceNameAqua",
+            Appearance::Dark => "NSAppearanceNameDarkAqua",
+        }
+    }
+}
+
+/// Where to insert a view among a container's existing subviews
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AttachPosition {
+    /// Below all existing subviews (as `add_glass_view` does)
+    #[default]
+    Bottom,
+    /// Above all existing subviews
+    Top,
+    /// Directly below a sibling subview (NSView* on macOS)
+    Below(*mut std::ffi::c_void),
+    /// Directly above a sibling subview (NSView* on macOS)
+    Above(*mut std::ffi::c_void),
+}
+
+/// Direction a separator runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Spans the container's width
+    Horizontal,
+    /// Spans the container's height
+    Vertical,
+}
+
+/// What `add_glass_view` does when the container already has a managed glass view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupPolicy {
+    /// Remove the existing view and add the new one
+    ReplaceExisting,
+    /// Add the new view on top of the existing one
+    #[default]
+    Allow,
+    /// Return `GlassError::AlreadyExists` with the existing view's ID
+    Error,
+}
+
+/// Description of a newly created glass view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlassViewHandle {
+    /// Unique view ID
+    pub id: i32,
+    /// Backend the view was created with
+    pub backend: GlassBackend,
+    /// Whether the tint is applied through the view's native `tintColor`
+    /// rather than the layer background color
+    pub native_tint: bool,
+}
+
+bitflags::bitflags! {
+    /// Set of corners that `corner_radius` applies to
+    ///
+    /// Bit values match `CACornerMask` so they can be passed to the layer directly.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
+    pub struct CornerMask: u64 {
+        /// Bottom-left corner (kCALayerMinXMinYCorner)
+        const BOTTOM_LEFT = 1 << 0;
+        /// Bottom-right corner (kCALayerMaxXMinYCorner)
+        const BOTTOM_RIGHT = 1 << 1;
+        /// Top-left corner (kCALayerMinXMaxYCorner)
+        const TOP_LEFT = 1 << 2;
+        /// Top-right corner (kCALayerMaxXMaxYCorner)
+        const TOP_RIGHT = 1 << 3;
+    }
+}
+
+impl Default for CornerMask {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Auto Layout insets pinning a view to its container's edges
+///
+/// Each present value constrains that edge to the container's matching edge,
+/// inset by the value in points. Absent edges are left free.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PinOptions {
+    /// Inset from the top edge
+    pub top: Option<f64>,
+    /// Inset from the leading edge
+    pub leading: Option<f64>,
+    /// Inset from the trailing edge
+    pub trailing: Option<f64>,
+    /// Inset from the bottom edge
+    pub bottom: Option<f64>,
+}
+
+impl PinOptions {
+    /// Pin every edge with the same inset
+    pub fn all(inset: f64) -> Self {
+        Self {
+            top: Some(inset),
+            leading: Some(inset),
+            trailing: Some(inset),
+            bottom: Some(inset),
+        }
+    }
+}
+
+/// When the tint is applied relative to inserting the view into its container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TintTiming {
+    /// Before `addSubview:`
+    BeforeAttach,
+    /// After `addSubview:`, which some fallback views need for the tint to stick
+    #[default]
+    AfterAttach,
+}
+
+/// How a tint layer blends with the layers beneath it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TintBlendMode {
+    /// Plain alpha compositing
+    #[default]
+    Normal,
+    /// Darkens, multiplying with what is beneath
+    Multiply,
+    /// Lightens, the inverse of multiply
+    Screen,
+    /// Multiply or screen depending on what is beneath
+    Overlay,
+    /// A gentler overlay
+    SoftLight,
+    /// Additive blending, brightening what is beneath
+    PlusLighter,
+}
+
+impl TintBlendMode {
+    /// Core Animation compositing filter name, or None for normal blending
+    pub fn compositing_filter(self) -> Option<&'static str> {
+        match self {
+            TintBlendMode::Normal => None,
+            TintBlendMode::Multiply => Some("multiplyBlendMode"),
+            TintBlendMode::Screen => Some("screenBlendMode"),
+            TintBlendMode::Overlay => Some("overlayBlendMode"),
+            TintBlendMode::SoftLight => Some("softLightBlendMode"),
+            TintBlendMode::PlusLighter => Some("plusL"),
+        }
+    }
+}
+
+/// A color layer stacked over the glass
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TintLayer {
+    /// Color in hex format (#RRGGBB or #RRGGBBAA), or CSS `oklch()`/`oklab()`
+    pub color: String,
+    /// How the layer blends with the glass and layers beneath it
+    pub blend_mode: TintBlendMode,
+}
+
+/// Drop shadow cast by a glass view
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Shadow {
+    /// Blur radius in points
+    pub radius: f64,
+    /// Opacity from 0.0 to 1.0
+    pub opacity: f64,
+    /// Horizontal offset in points
+    pub offset_x: f64,
+    /// Vertical offset in points (negative is downward)
+    pub offset_y: f64,
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self {
+            radius: 10.0,
+            opacity: 0.3,
+            offset_x: 0.0,
+            offset_y: -2.0,
+        }
+    }
+}
+
+/// Environment the process is rendering in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderingEnvironment {
+    /// Running natively on the host hardware
+    Native,
+    /// An x86_64 process translated by Rosetta 2
+    Rosetta,
+    /// A virtual machine, where glass may render poorly or not at all
+    VirtualMachine,
+}
+
+/// What the running system supports, as seen by a manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlassCapabilities {
+    /// Whether native glass (NSGlassEffectView) is available
+    pub native_glass: bool,
+    /// Whether "Reduce Transparency" is currently on
+    pub reduce_transparency: bool,
+    /// Whether "Reduce Motion" is currently on
+    pub reduce_motion: bool,
+    /// Native, Rosetta, or virtual machine
+    pub environment: RenderingEnvironment,
+}
+
+/// Crate version, compiled features and runtime capabilities, for bug reports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// Version of this crate
+    pub crate_version: &'static str,
+    /// Cargo features this crate was compiled with
+    pub features: Vec<&'static str>,
+    /// What the running system supports
+    pub capabilities: GlassCapabilities,
+}
+
+/// Rough memory cost of managed views, from [`GlassViewManager::estimated_footprint`]
+///
+/// This is an estimate, not a measurement: every layer is assumed to keep a
+/// full-size 4-byte-per-pixel backing store, while the window server may
+/// share, compress or skip some of them and glass keeps extra private
+/// buffers. Use it to compare configurations, not to budget exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FootprintEstimate {
+    /// Number of views counted
+    pub views: usize,
+    /// Number of view-sized layers across all views
+    pub layers: usize,
+    /// Estimated bytes across all views
+    pub bytes: u64,
+}
+
+impl FootprintEstimate {
+    /// Estimated size in mebibytes
+    pub fn megabytes(&self) -> f64 {
+        self.bytes as f64 / (1024.0 * 1024.0)
+    }
+}
+
+/// Access to a manager inside [`GlassViewManager::transaction`]
+pub struct TransactionScope<'a> {
+    manager: &'a GlassViewManager,
+}
+
+impl std::ops::Deref for TransactionScope<'_> {
+    type Target = GlassViewManager;
+
+    fn deref(&self) -> &GlassViewManager {
+        self.manager
+    }
+}
+
+/// A view targeted by an operation in a [`GlassBatch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchTarget {
+    /// An existing view
+    View(i32),
+    /// The view created by an earlier `add` in the same batch
+    Added(usize),
+}
+
+impl From<i32> for BatchTarget {
+    fn from(view_id: i32) -> Self {
+        BatchTarget::View(view_id)
+    }
+}
+
+/// An operation recorded in a [`GlassBatch`]
+#[derive(Debug, Clone)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+enum BatchOp {
+    Add(*mut std::ffi::c_void, Box<GlassOptions>),
+    SetVariant(BatchTarget, GlassMaterialVariant),
+    SetScrimState(BatchTarget, i64),
+    SetSubduedState(BatchTarget, i64),
+    SetCornerRadius(BatchTarget, f64),
+    Remove(BatchTarget),
+}
+
+/// Operations recorded up front and applied together by [`commit`](Self::commit)
+///
+/// Nothing touches the native views until `commit`, which runs every
+/// operation under one lock, one main-thread check and one `CATransaction`.
+/// Later operations can target views added earlier in the batch through the
+/// [`BatchTarget`] returned by [`add`](Self::add).
+#[derive(Debug, Clone, Default)]
+pub struct GlassBatch {
+    ops: Vec<BatchOp>,
+}
+
+impl GlassBatch {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of recorded operations
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether no operations have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Record adding a glass view to a container (NSView* on macOS)
+    pub fn add(&mut self, window_handle: *mut std::ffi::c_void, options: GlassOptions) -> BatchTarget {
+        self.ops.push(BatchOp::Add(window_handle, Box::new(options)));
+        BatchTarget::Added(self.ops.len() - 1)
+    }
+
+    /// Record setting a view's material variant
+    pub fn set_variant(&mut self, target: impl Into<BatchTarget>, variant: GlassMaterialVariant) -> &mut Self {
+        self.ops.push(BatchOp::SetVariant(target.into(), variant));
+        self
+    }
+
+    /// Record setting a view's scrim state
+    pub fn set_scrim_state(&mut self, target: impl Into<BatchTarget>, state: i64) -> &mut Self {
+        self.ops.push(BatchOp::SetScrimState(target.into(), state));
+        self
+    }
+
+    /// Record setting a view's subdued state
+    pub fn set_subdued_state(&mut self, target: impl Into<BatchTarget>, state: i64) -> &mut Self {
+        self.ops.push(BatchOp::SetSubduedState(target.into(), state));
+        self
+    }
+
+    /// Record changing a view's corner radius
+    pub fn set_corner_radius(&mut self, target: impl Into<BatchTarget>, radius: f64) -> &mut Self {
+        self.ops.push(BatchOp::SetCornerRadius(target.into(), radius));
+        self
+    }
+
+    /// Record removing a view
+    pub fn remove(&mut self, target: impl Into<BatchTarget>) -> &mut Self {
+        self.ops.push(BatchOp::Remove(target.into()));
+        self
+    }
+
+    /// Apply every operation in order, returning one result per operation
+    ///
+    /// Adds yield `Some(view_id)`, everything else `None`. A failed operation
+    /// does not stop the rest; operations targeting a failed add fail with
+    /// `GlassError::InvalidState`. Must be called from the main thread.
+    pub fn commit(self, manager: &GlassViewManager) -> Vec<Result<Option<i32>>> {
+        manager.commit_batch(self.ops)
+    }
 }
 
 /// Configuration options for glass views
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GlassOptions {
-    /// Corner radius in points (default: 0.0)
+    /// Corner radius in points (default: 0.0). Negative values are treated as 0.
     pub corner_radius: f64,
-    /// Tint color in hex format (#RRGGBB or #RRGGBBAA)
+    /// Corners the radius is applied to (default: all)
+    pub round_corners: CornerMask,
+    /// Initial frame in the container's coordinates (default: None, fill the
+    /// container). Views with an explicit frame do not autoresize.
+    pub frame: Option<Rect>,
+    /// Tint color in hex format (#RRGGBB or #RRGGBBAA), or as CSS
+    /// `oklch(L C H [/ A])` or `oklab(L a b [/ A])`, clamped to sRGB
     pub tint_color: Option<String>,
     /// Whether to add an opaque background layer
     pub opaque: bool,
+    /// Identifier set on the NSView and used in log messages
+    pub identifier: Option<String>,
+    /// Explicitly enable or disable layer backing (default: None, layer-backed
+    /// only when needed). A non-zero `corner_radius` always enables it.
+    pub wants_layer: Option<bool>,
+    /// Insert with plain `addSubview:` (append on top) instead of the
+    /// positioned form when there is no sibling to position against
+    /// (default: false). Works around z-order surprises in some embeddings.
+    pub plain_add_subview: bool,
+    /// Whether the glass view itself can become first responder and join the
+    /// key view loop (default: false, so focus passes to its content)
+    pub accepts_first_responder: bool,
+    /// Drop shadow (default: None). With a non-zero `corner_radius` the shadow
+    /// is cast by a separate view beneath the glass, so the corner clipping
+    /// does not cut it off.
+    pub shadow: Option<Shadow>,
+    /// Pin edges to the container with Auto Layout instead of autoresizing
+    /// (default: None). Pinned views ignore `sync_bounds`.
+    pub pin: Option<PinOptions>,
+    /// Color of a 1px highlight along the top edge, following the corner
+    /// radius, in hex format (default: None)
+    pub rim_highlight: Option<String>,
+    /// Corner radius of the opaque background (default: None, match
+    /// `corner_radius`)
+    pub background_corner_radius: Option<f64>,
+    /// When the tint is applied relative to attaching the view (default:
+    /// after). Detached views are tinted once attached.
+    pub tint_timing: TintTiming,
+    /// Blur radius in points for the `BlurOnly` backend (default: 20.0)
+    pub blur_radius: f64,
+    /// Override the view's `allowsVibrancy`, so content inside it blends
+    /// vibrantly with the backdrop (default: None, keep the class default)
+    pub allows_vibrancy: Option<bool>,
+    /// Use the system's window corner radius instead of `corner_radius`, for
+    /// glass that sits at the window edge (default: false)
+    pub match_window_corners: bool,
+    /// Color layers stacked in order over the glass, clipped to the corner
+    /// radius (default: none)
+    pub tint_layers: Vec<TintLayer>,
+    /// Keep width / height at this ratio with an Auto Layout constraint, e.g.
+    /// 1.0 for a square (default: None). Only used with `pin`; leave at
+    /// least one edge unpinned so the constraints can be satisfied.
+    pub aspect_ratio: Option<f64>,
+    /// Tint used instead of `tint_color` when the view has no native tint and
+    /// the color goes on its layer background, which renders differently
+    /// (default: None, use `tint_color`)
+    pub fallback_tint_color: Option<String>,
+    /// Opacity (0 to 1) of a fine noise texture over the glass for a frosted
+    /// look, clipped to the corner radius (default: None). Around 0.05 is subtle.
+    pub grain_intensity: Option<f64>,
+    /// Backend to create the view with (default: None, native glass when
+    /// available, otherwise the fallback). The accessibility policy still
+    /// takes precedence and restores this backend once it no longer applies.
+    pub backend: Option<GlassBackend>,
 }
 
 impl Default for GlassOptions {
     fn default() -> Self {
         Self {
             corner_radius: 0.0,
+            round_corners: CornerMask::all(),
+            frame: None,
             tint_color: None,
             opaque: false,
+            identifier: None,
+            wants_layer: None,
+            plain_add_subview: false,
+            accepts_first_responder: false,
+            shadow: None,
+            pin: None,
+            rim_highlight: None,
+            background_corner_radius: None,
+            tint_timing: TintTiming::AfterAttach,
+            blur_radius: 20.0,
+            allows_vibrancy: None,
+            match_window_corners: false,
+            tint_layers: Vec::new(),
+            aspect_ratio: None,
+            fallback_tint_color: None,
+            grain_intensity: None,
+            backend: None,
         }
     }
 }
 
+impl GlassOptions {
+    /// Clamp out-of-range values
+    ///
+    /// Negative (or NaN) corner and blur radii become 0, aspect ratios that
+    /// are not positive and finite are dropped, and grain intensities are
+    /// clamped to 0..=1 (NaN is dropped). The manager applies this to every
+    /// options value it is given.
+    pub fn normalized(mut self) -> Self {
+        self.corner_radius = self.corner_radius.max(0.0);
+        self.background_corner_radius = self.background_corner_radius.map(|radius| radius.max(0.0));
+        self.blur_radius = self.blur_radius.max(0.0);
+        self.aspect_ratio = self.aspect_ratio.filter(|ratio| ratio.is_finite() && *ratio > 0.0);
+        self.grain_intensity = self
+            .grain_intensity
+            .filter(|intensity| !intensity.is_nan())
+            .map(|intensity| intensity.clamp(0.0, 1.0));
+        self
+    }
+}
+
 /// Manager for creating and manipulating glass effect views
 pub struct GlassViewManager {
     #[cfg(target_os = "macos")]
@@ -95,9 +787,11 @@ impl GlassViewManager {
     pub fn new() -> Self {
         #[cfg(target_os = "macos")]
         {
-            Self {
+            let manager = Self {
                 inner: Arc::new(Mutex::new(crate::macos::MacOSGlassManager::new())),
-            }
+            };
+            manager.observe_window_moves();
+            manager
         }
         
         #[cfg(not(target_os = "macos"))]
@@ -108,42 +802,110 @@ impl GlassViewManager {
         }
     }
 
-    /// Check if glass effects are supported on this platform
-    pub fn is_supported(&self) -> bool {
+    /// Create a manager that takes default options from the environment
+    ///
+    /// For tuning the look without recompiling. When a view is created with
+    /// a field left at its default, these variables override it:
+    ///
+    /// - `GLASS_DEFAULT_CORNER_RADIUS`: `corner_radius`, e.g. `24`
+    /// - `GLASS_DEFAULT_TINT_COLOR`: `tint_color`, e.g. `#ffffff20`
+    /// - `GLASS_DEFAULT_BLUR_RADIUS`: `blur_radius`
+    /// - `GLASS_DEFAULT_VARIANT`: raw material variant set on every new
+    ///   native glass view, e.g. `2` for [`GlassMaterialVariant::Dock`]
+    ///
+    /// Variables are read once, here. Invalid values are logged and ignored.
+    pub fn new_with_env() -> Self {
+        let manager = Self::new();
+
+        #[cfg(target_os = "macos")]
+        manager
+            .inner
+            .lock()
+            .unwrap()
+            .set_env_defaults(crate::macos::EnvDefaults::from_env());
+
+        manager
+    }
+
+    /// Create a glass view manager that skips support detection
+    ///
+    /// `is_supported` always returns `true` on macOS without probing the
+    /// runtime. Only use this when the caller guarantees macOS 15+.
+    pub fn new_assume_supported() -> Self {
+        let manager = Self::new();
+
+        #[cfg(target_os = "macos")]
+        manager.inner.lock().unwrap().set_assume_supported(true);
+
+        manager
+    }
+
+    /// Reapply each view's options when it moves to another window
+    #[cfg(target_os = "macos")]
+    fn observe_window_moves(&self) {
+        let weak = Arc::downgrade(&self.inner);
+        let observer = crate::macos::NotificationObserver::view_did_move_to_window(move |view| {
+            // The move may happen inside a call that already holds the lock,
+            // such as add_glass_view, which configures the view itself
+            if let Some(inner) = weak.upgrade()
+                && let Ok(mut inner) = inner.try_lock()
+            {
+                inner.reapply_native_view(view);
+            }
+        });
+        self.inner.lock().unwrap().set_window_move_observer(observer);
+    }
+
+    /// Reapply a view's options, variant and states to its native view
+    ///
+    /// Done automatically when a view moves to another window, such as a
+    /// panel being torn off. Must be called from the main thread.
+    pub fn reapply(&self, view_id: i32) -> Result<()> {
         #[cfg(target_os = "macos")]
         {
-            self.inner.lock().unwrap().is_supported()
+            self.inner.lock().unwrap().reapply(view_id)
         }
-        
+
         #[cfg(not(target_os = "macos"))]
         {
-            false
+            Err(GlassError::UnsupportedPlatform)
         }
     }
 
-    /// Add a glass effect view to a window
-    ///
-    /// # Arguments
-    /// * `window_handle` - Pointer to the native window (NSView* on macOS)
-    /// * `options` - Configuration options for the glass effect
+    /// Change a live view's options without removing and re-adding it
     ///
-    /// # Returns
-    /// A unique view ID that can be used to manipulate the view later, or an error
-    pub fn add_glass_view(
-        &self,
-        window_handle: *mut std::ffi::c_void,
-        options: GlassOptions,
-    ) -> Result<i32> {
-        if window_handle.is_null() {
-            return Err(GlassError::InvalidHandle);
+    /// Reconfigures the existing native view, so there is no flicker. Tint,
+    /// identifier, corner radius, shadow, rim highlight, tint layers, grain,
+    /// `accepts_first_responder`, `allows_vibrancy` and `wants_layer` follow
+    /// the new options, including being cleared when unset, and the opaque
+    /// background is added or removed in place. A different `backend` switches
+    /// it as [`set_backend`](Self::set_backend) does (after `resume_all`, or
+    /// once the accessibility policy no longer applies, if either holds the
+    /// view on the solid color backend); `None` keeps the current one.
+    /// Placement options (`frame`, `pin`, `aspect_ratio`) are not re-applied.
+    /// Must be called from the main thread.
+    pub fn update_glass_view(&self, view_id: i32, options: GlassOptions) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().update_glass_view(view_id, options)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
         }
+    }
 
+    /// Create glass views as instances of the runtime class `name`
+    ///
+    /// For custom `NSGlassEffectView` subclasses registered with the
+    /// Objective-C runtime. Returns [`GlassError::ClassNotFound`] if the class
+    /// does not exist and [`GlassError::InvalidClass`] if it does not inherit
+    /// from `NSGlassEffectView`. Only views added afterwards are affected.
+    pub fn set_glass_class_name(&self, name: &str) -> Result<()> {
         #[cfg(target_os = "macos")]
         {
-            self.inner
-                .lock()
-                .unwrap()
-                .add_glass_view(window_handle, options)
+            self.inner.lock().unwrap().set_glass_class_name(name)
         }
 
         #[cfg(not(target_os = "macos"))]
@@ -152,16 +914,16 @@ impl GlassViewManager {
         }
     }
 
-    /// Set the glass material variant for a view
+    /// Outline every managed view and label it with its ID (default: off)
     ///
-    /// This is an experimental API that uses private macOS APIs
-    pub fn set_variant(&self, view_id: i32, variant: GlassMaterialVariant) -> Result<()> {
+    /// A development aid for seeing exactly where each glass region is. Each
+    /// view gets a 1px border in its own color and its ID (and identifier, if
+    /// set) in the top-left corner; views added while enabled get one too.
+    /// Disabling removes them all. Must be called from the main thread.
+    pub fn set_debug_overlay(&self, enabled: bool) -> Result<()> {
         #[cfg(target_os = "macos")]
         {
-            self.inner
-                .lock()
-                .unwrap()
-                .set_variant(view_id, variant)
+            self.inner.lock().unwrap().set_debug_overlay(enabled)
         }
 
         #[cfg(not(target_os = "macos"))]
@@ -170,43 +932,1750 @@ impl GlassViewManager {
         }
     }
 
-    /// Set the scrim state for a view (0 = none, 1 = light, 2 = dark)
-    pub fn set_scrim_state(&self, view_id: i32, state: i64) -> Result<()> {
+    /// Reuse the IDs of removed views for new views (default: off)
+    ///
+    /// When enabled, new views take the smallest freed ID so IDs stay compact
+    /// over long sessions. Without recycling, IDs increase monotonically and
+    /// `add_glass_view` returns [`GlassError::LimitExceeded`] once exhausted.
+    pub fn set_recycle_ids(&self, recycle: bool) {
         #[cfg(target_os = "macos")]
         {
-            self.inner
-                .lock()
-                .unwrap()
-                .set_int_property(view_id, "scrimState", state)
+            self.inner.lock().unwrap().set_recycle_ids(recycle)
+        }
+    }
+
+    /// Choose what happens when adding glass to a container that already has some
+    pub fn set_dedup_policy(&self, policy: DedupPolicy) {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_dedup_policy(policy)
+        }
+    }
+
+    /// Collect the crate version, compiled features and runtime capabilities
+    ///
+    /// Its `Debug` output is meant to be pasted into bug reports.
+    pub fn version_info(&self) -> VersionInfo {
+        let mut features = Vec::new();
+        if cfg!(feature = "serde") {
+            features.push("serde");
+        }
+        if cfg!(feature = "async") {
+            features.push("async");
+        }
+        if cfg!(feature = "variant-as-int") {
+            features.push("variant-as-int");
+        }
+
+        VersionInfo {
+            crate_version: env!("CARGO_PKG_VERSION"),
+            features,
+            capabilities: GlassCapabilities {
+                native_glass: self.is_supported(),
+                reduce_transparency: self.reduce_transparency_enabled(),
+                reduce_motion: self.reduce_motion_enabled(),
+                environment: self.rendering_environment(),
+            },
+        }
+    }
+
+    /// Best-effort corner radius of standard windows on the running macOS
+    ///
+    /// Based on the OS version: 16pt on macOS 26, 10pt on macOS 11 to 15 and
+    /// 5pt before. Windows with toolbars may be rounder on macOS 26. Returns 0
+    /// on other platforms.
+    pub fn system_window_corner_radius(&self) -> f64 {
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::system_window_corner_radius()
         }
 
         #[cfg(not(target_os = "macos"))]
         {
-            Err(GlassError::UnsupportedPlatform)
+            0.0
         }
     }
 
-    /// Set the subdued state for a view
-    pub fn set_subdued_state(&self, view_id: i32, state: i64) -> Result<()> {
+    /// Report whether the process runs natively, under Rosetta, or in a VM
+    ///
+    /// Glass can render poorly in virtual machines; consider
+    /// [`GlassBackend::SolidColor`] there. Always `Native` on other platforms.
+    pub fn rendering_environment(&self) -> RenderingEnvironment {
         #[cfg(target_os = "macos")]
         {
-            self.inner
-                .lock()
-                .unwrap()
-                .set_int_property(view_id, "subduedState", state)
+            crate::macos::rendering_environment()
         }
 
         #[cfg(not(target_os = "macos"))]
         {
-            Err(GlassError::UnsupportedPlatform)
+            RenderingEnvironment::Native
         }
     }
 
-    /// Remove a glass view by ID
-    pub fn remove_view(&self, view_id: i32) -> Result<()> {
+    /// Check if glass effects are supported on this platform
+    ///
+    /// Support is probed on every call and never cached; `add_glass_view` also
+    /// picks its backend per call. Note that a running process keeps the
+    /// AppKit it was launched with, so an OS update only takes effect for
+    /// processes started after it.
+    pub fn is_supported(&self) -> bool {
         #[cfg(target_os = "macos")]
         {
-            self.inner.lock().unwrap().remove_view(view_id)
+            self.inner.lock().unwrap().is_supported()
+        }
+        
+        #[cfg(not(target_os = "macos"))]
+        {
+            false
+        }
+    }
+
+    /// Check whether the "Reduce Transparency" accessibility setting is on
+    pub fn reduce_transparency_enabled(&self) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().reduce_transparency_enabled()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            false
+        }
+    }
+
+    /// Make `add_glass_view` use the solid color backend while "Reduce
+    /// Transparency" is enabled (default: off)
+    pub fn set_honor_reduce_transparency(&self, honor: bool) {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_honor_reduce_transparency(honor)
+        }
+    }
+
+    /// Check whether the "Reduce Motion" accessibility setting is on
+    pub fn reduce_motion_enabled(&self) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().reduce_motion_enabled()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            false
+        }
+    }
+
+    /// Set how accessibility display settings are honored
+    ///
+    /// While reduce-transparency is honored and enabled, new and existing views
+    /// use the solid color backend; live views switch back when the setting is
+    /// turned off. While reduce-motion is honored and enabled, animated changes
+    /// apply instantly. Settings changes are observed automatically.
+    pub fn set_accessibility_policy(&self, policy: AccessibilityPolicy) {
+        #[cfg(target_os = "macos")]
+        {
+            let mut inner = self.inner.lock().unwrap();
+            inner.set_accessibility_policy(policy);
+            inner.ensure_accessibility_observer(|| {
+                let weak = Arc::downgrade(&self.inner);
+                crate::macos::NotificationObserver::accessibility_display_options_changed(
+                    move || {
+                        if let Some(inner) = weak.upgrade() {
+                            inner.lock().unwrap().apply_accessibility_policy();
+                        }
+                    },
+                )
+            });
+            inner.apply_accessibility_policy();
+        }
+    }
+
+    /// Whether animated changes should run, given the accessibility policy
+    pub fn animations_allowed(&self) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().animations_allowed()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            true
+        }
+    }
+
+    /// Add a glass effect view to a window
+    ///
+    /// # Arguments
+    /// * `window_handle` - Pointer to the native window (NSView* on macOS)
+    /// * `options` - Configuration options for the glass effect
+    ///
+    /// # Returns
+    /// A unique view ID that can be used to manipulate the view later, or an error
+    pub fn add_glass_view(
+        &self,
+        window_handle: *mut std::ffi::c_void,
+        options: GlassOptions,
+    ) -> Result<i32> {
+        if window_handle.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .add_glass_view(window_handle, options)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Add a glass effect view at a chosen z-position among the container's subviews
+    ///
+    /// [`AttachPosition::Below`] and [`AttachPosition::Above`] place the glass
+    /// relative to a specific sibling; an opaque background goes directly
+    /// beneath the glass.
+    pub fn add_glass_view_at(
+        &self,
+        window_handle: *mut std::ffi::c_void,
+        options: GlassOptions,
+        position: AttachPosition,
+    ) -> Result<i32> {
+        if window_handle.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .add_glass_view_at(window_handle, options, position)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Add a copy of a view to another container, returning the new view's ID
+    ///
+    /// The copy gets the source's options, variant, scrim and subdued state.
+    /// Callbacks and other runtime-only state are not copied.
+    pub fn duplicate(&self, view_id: i32, new_container: *mut std::ffi::c_void) -> Result<i32> {
+        if new_container.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().duplicate(view_id, new_container)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Add a glass effect view to a window, returning details about the created view
+    ///
+    /// Same as [`add_glass_view`](Self::add_glass_view), but also reports the
+    /// backend that was chosen and how the tint is applied.
+    pub fn add_glass_view_detailed(
+        &self,
+        window_handle: *mut std::ffi::c_void,
+        options: GlassOptions,
+    ) -> Result<GlassViewHandle> {
+        if window_handle.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .add_glass_view_detailed(window_handle, options)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Add a non-scrolling glass background to a scroll view (NSScrollView* on macOS)
+    ///
+    /// The glass is inserted below the scroll view's clip view and kept at
+    /// the clip view's frame, so it covers exactly the visible content area,
+    /// stays put while the document scrolls and does not extend under the
+    /// scrollers. The scroll and clip views stop drawing their backgrounds so
+    /// the glass shows through, and draw them again once the glass is
+    /// removed. `frame` and `pin` in `options` are ignored.
+    /// Returns `GlassError::InvalidHandle` if the pointer is not a scroll view.
+    pub fn add_glass_to_scroll_view(
+        &self,
+        scroll_view_ptr: *mut std::ffi::c_void,
+        options: GlassOptions,
+    ) -> Result<i32> {
+        if scroll_view_ptr.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let weak = Arc::downgrade(&self.inner);
+            self.inner.lock().unwrap().add_glass_to_scroll_view(
+                scroll_view_ptr,
+                options,
+                Box::new(move |view_id| {
+                    // Look the view up on every change, so it follows backend
+                    // swaps; skip changes caused by a call that holds the lock
+                    if let Some(inner) = weak.upgrade()
+                        && let Ok(inner) = inner.try_lock()
+                    {
+                        let _ = inner.sync_clip_frame(view_id);
+                    }
+                }),
+            )
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Add a glass view behind a window's titlebar (NSWindow* on macOS)
+    ///
+    /// The glass sits below the window buttons and any titlebar accessory
+    /// views. Returns `GlassError::InvalidState` for windows without a titlebar.
+    pub fn add_glass_to_titlebar(
+        &self,
+        window_ptr: *mut std::ffi::c_void,
+        options: GlassOptions,
+    ) -> Result<i32> {
+        if window_ptr.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .add_glass_to_titlebar(window_ptr, options)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Prepare a window (NSWindow* on macOS) for glass content
+    ///
+    /// Makes the window non-opaque with a clear background, so glass used as
+    /// its content view blurs the desktop instead of rendering black, and
+    /// keeps the window shadow. Must be called from the main thread.
+    pub fn prepare_window_for_glass(&self, window_ptr: *mut std::ffi::c_void) -> Result<()> {
+        if window_ptr.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().prepare_window_for_glass(window_ptr)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Make a glass view the content view of a window (NSWindow* on macOS)
+    ///
+    /// The window's existing content view is moved inside the glass, and is
+    /// restored as the content view by [`remove_view`](Self::remove_view).
+    /// The `opaque` option is ignored for content views.
+    pub fn set_as_content_view(
+        &self,
+        window_ptr: *mut std::ffi::c_void,
+        options: GlassOptions,
+    ) -> Result<i32> {
+        if window_ptr.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .set_as_content_view(window_ptr, options)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Place a content view (NSView* on macOS) inside a glass view
+    ///
+    /// Native glass views take it through their dedicated `contentView`
+    /// property so it renders above the glass; other backends fall back to
+    /// `addSubview:`.
+    pub fn set_glass_content(&self, view_id: i32, content: *mut std::ffi::c_void) -> Result<()> {
+        if content.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_glass_content(view_id, content)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Create and configure a glass view without adding it to a container
+    ///
+    /// The view is tracked like any other, and can be inserted into a
+    /// hierarchy later with [`attach_view`](Self::attach_view).
+    pub fn create_detached_glass_view(&self, bounds: Rect, options: GlassOptions) -> Result<i32> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .create_detached_glass_view(bounds, options)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Add a hairline separator to a container (NSView* on macOS)
+    ///
+    /// `position` is the separator's offset along the other axis, in the
+    /// container's coordinates. The separator gets its own ID and is removed
+    /// with [`remove_view`](Self::remove_view), but is not treated as the
+    /// container's glass: handle lookup, dedup, backend changes and
+    /// suspension skip it.
+    pub fn add_separator(
+        &self,
+        container_ptr: *mut std::ffi::c_void,
+        orientation: Orientation,
+        position: f64,
+    ) -> Result<i32> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .add_separator(container_ptr, orientation, position)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Add glass segments that merge into one continuous shape, returning
+    /// one ID per frame
+    ///
+    /// For segmented toolbars: each frame (in the container's coordinates)
+    /// gets its own glass view, and the segments share an
+    /// `NSGlassEffectContainerView` so adjacent ones blend together. Every
+    /// segment uses `options`, except that `frame` is replaced and `pin` is
+    /// ignored. Without `NSGlassEffectContainerView` (before macOS 26) the
+    /// segments are added side by side without merging. The shared container
+    /// is removed with the last segment.
+    pub fn add_glass_group(
+        &self,
+        container: *mut std::ffi::c_void,
+        frames: Vec<Rect>,
+        options: GlassOptions,
+    ) -> Result<Vec<i32>> {
+        if container.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .add_glass_group(container, frames, options)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Insert a view into a container (NSView* on macOS)
+    pub fn attach_view(
+        &self,
+        view_id: i32,
+        container: *mut std::ffi::c_void,
+        position: AttachPosition,
+    ) -> Result<()> {
+        if container.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .attach_view(view_id, container, position)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Whether a view currently has a tint
+    ///
+    /// True if it has a `tint_color` (including one set by
+    /// [`set_adaptive_tint`](Self::set_adaptive_tint)) or any `tint_layers`.
+    /// Answered from the cached options without touching the native view.
+    pub fn has_tint(&self, view_id: i32) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().has_tint(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Read back the tint actually applied to a view as sRGB `(r, g, b, a)`
+    ///
+    /// Returns `None` if the view has no tint or the color cannot be read back.
+    pub fn resolved_tint(&self, view_id: i32) -> Result<Option<(f64, f64, f64, f64)>> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().resolved_tint(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Tint a view with `base_hex`, darkened over bright backdrops and
+    /// lightened over dark ones
+    ///
+    /// `contrast` (0.0..=1.0) scales the adjustment. The backdrop is sampled
+    /// from the window content behind the view, so it does not see the
+    /// desktop behind a transparent window. Sampling happens now and on
+    /// [`refresh_adaptive_tint`](Self::refresh_adaptive_tint). Must be called
+    /// from the main thread.
+    pub fn set_adaptive_tint(&self, view_id: i32, base_hex: &str, contrast: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .set_adaptive_tint(view_id, base_hex, contrast)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Re-sample the backdrop and update the tint set by
+    /// [`set_adaptive_tint`](Self::set_adaptive_tint)
+    pub fn refresh_adaptive_tint(&self, view_id: i32) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().refresh_adaptive_tint(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Grey out a view to show it is disabled, or restore its normal look
+    ///
+    /// Disabling halves the view's alpha, sets the subdued state and swaps the
+    /// tint for a desaturated copy. The view's options are left untouched, so
+    /// enabling restores the original alpha, subdued state and tint. Calling
+    /// this with the current state does nothing.
+    pub fn set_enabled_appearance(&self, view_id: i32, enabled: bool) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_enabled_appearance(view_id, enabled)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Pixels per point for a view, e.g. 2.0 on Retina displays
+    ///
+    /// Read from the view's window, or from the main screen if the view is
+    /// not in a window yet.
+    pub fn backing_scale(&self, view_id: i32) -> Result<f64> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().backing_scale(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Whether a view's layer clips to its bounds (`masksToBounds`)
+    ///
+    /// A clipping layer hides its own shadow, which is why rounded views get
+    /// a separate shadow view. False if the view is not layer-backed.
+    pub fn is_clipping(&self, view_id: i32) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().is_clipping(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the Core Animation `zPosition` of a view's layer
+    ///
+    /// Layers with a higher z are drawn above their siblings regardless of
+    /// subview order, so a glass element can float above other views without
+    /// reordering the container's subviews. Siblings must be layer-backed too.
+    /// The view's shadow, opaque background and frozen snapshot move with it.
+    pub fn set_z_position(&self, view_id: i32, z: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_z_position(view_id, z)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Allow or prevent extended dynamic range (HDR) rendering for a view
+    ///
+    /// Disabling it keeps the tint in standard dynamic range, which avoids a
+    /// washed-out look on some HDR displays. Requires macOS 14 or later.
+    pub fn set_edr_enabled(&self, view_id: i32, enabled: bool) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_edr_enabled(view_id, enabled)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Advance a view to the next variant in [`GlassMaterialVariant::all`],
+    /// wrapping around, and return it
+    ///
+    /// Starts from the last variant set, or `Regular` if none was. A raw
+    /// value without a named variant moves to `Regular`. Handy for demos and
+    /// variant browsers.
+    pub fn cycle_variant(&self, view_id: i32) -> Result<GlassMaterialVariant> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().cycle_variant(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the glass material variant for a view
+    ///
+    /// This is an experimental API that uses private macOS APIs
+    pub fn set_variant(&self, view_id: i32, variant: GlassMaterialVariant) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .set_variant(view_id, variant)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Change the corner radius of a view instantly (negative values become 0)
+    ///
+    /// Must be called from the main thread.
+    pub fn set_corner_radius(&self, view_id: i32, radius: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_corner_radius(view_id, radius)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the corner radius to `from + (to - from) * t`, with `t` clamped to 0..=1
+    ///
+    /// For driving the shape from a slider or gesture progress; applied
+    /// instantly, like [`set_corner_radius`](Self::set_corner_radius).
+    pub fn set_corner_radius_lerp(&self, view_id: i32, from: f64, to: f64, t: f64) -> Result<()> {
+        let t = t.clamp(0.0, 1.0);
+        self.set_corner_radius(view_id, from + (to - from) * t)
+    }
+
+    /// Set the glass material variant from a raw integer
+    ///
+    /// For experimenting with variants newer than [`GlassMaterialVariant`].
+    /// The value is passed to the private setter unchecked and recorded as
+    /// [`GlassMaterialVariant::Unknown`] unless it has a named variant. This
+    /// is an experimental API that uses private macOS APIs.
+    pub fn set_variant_raw(&self, view_id: i32, value: i64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_variant_raw(view_id, value)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the thickness (effect strength) of a native glass view
+    ///
+    /// This is an experimental API that uses private macOS APIs: the value is
+    /// passed to a `thickness` or `intensity` property if the view has one,
+    /// and an error is returned otherwise. The meaningful range is unknown.
+    pub fn set_thickness(&self, view_id: i32, value: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_thickness(view_id, value)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the scrim state for a view (0 = none, 1 = light, 2 = dark)
+    pub fn set_scrim_state(&self, view_id: i32, state: i64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .set_scrim_state(view_id, state)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the subdued state for a view
+    pub fn set_subdued_state(&self, view_id: i32, state: i64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .set_subdued_state(view_id, state)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Swap the backend of an existing view at runtime
+    ///
+    /// The current view is torn down and rebuilt with the chosen backend in
+    /// place, reapplying the options it was created with. The view ID is kept,
+    /// and so are its content, constraints, alpha, z-position, layer opacity,
+    /// appearance override, dynamic range, callbacks, its emphasized state
+    /// (if the new view has one) and (for native glass) its variant, states
+    /// and thickness.
+    pub fn set_backend(&self, view_id: i32, backend: GlassBackend) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_backend(view_id, backend)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Install callbacks fired when the pointer enters or leaves a view
+    ///
+    /// An `NSTrackingArea` is added to the view and removed again by
+    /// [`remove_view`](Self::remove_view). Calling this again replaces the
+    /// previous callbacks. Must be called from the main thread.
+    pub fn set_hover_callbacks<F, G>(&self, view_id: i32, on_enter: F, on_exit: G) -> Result<()>
+    where
+        F: Fn() + 'static,
+        G: Fn() + 'static,
+    {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_hover_callbacks(
+                view_id,
+                Box::new(on_enter),
+                Box::new(on_exit),
+            )
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Replace a view with a static snapshot of its current rendering
+    ///
+    /// Avoids live-blur flicker during heavy transitions such as animated
+    /// resizes. The snapshot is what AppKit can cache for the view, so blur
+    /// composited by the window server may not be captured. Freezing a frozen
+    /// view does nothing. Must be called from the main thread.
+    pub fn freeze(&self, view_id: i32) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().freeze(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Remove the snapshot added by [`freeze`](Self::freeze) and show the live view
+    pub fn unfreeze(&self, view_id: i32) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().unfreeze(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Choose whether a view blurs the desktop or in-window content
+    ///
+    /// Applies to the NSVisualEffectView fallback and to native glass views
+    /// that expose a blending mode; returns an error for views that do not.
+    pub fn set_blending_mode(&self, view_id: i32, mode: BlendingMode) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_blending_mode(view_id, mode)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Composite the glass over a fixed background image
+    ///
+    /// The PNG is placed in an image view directly beneath the glass and the
+    /// glass switches to in-window blending, giving a wallpaper-independent
+    /// look. Calling this again replaces the previous image.
+    pub fn set_background_image(&self, view_id: i32, png_bytes: &[u8]) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_background_image(view_id, png_bytes)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Resize a view to fill its container immediately
+    ///
+    /// Views created with an explicit `frame` are left untouched.
+    pub fn sync_bounds(&self, view_id: i32) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().sync_bounds(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Keep a view sized to its container as its window resizes
+    ///
+    /// Calls [`sync_bounds`](Self::sync_bounds) on every
+    /// `NSWindowDidResizeNotification` of the view's window, for containers
+    /// whose autoresizing does not reach the glass. The view must already be
+    /// in a window; [`remove_view`](Self::remove_view) stops tracking. Must be
+    /// called from the main thread.
+    pub fn auto_track_window(&self, view_id: i32) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            let weak = Arc::downgrade(&self.inner);
+            self.inner.lock().unwrap().auto_track_window(
+                view_id,
+                Box::new(move || {
+                    // Skip resizes caused by a call that holds the lock
+                    if let Some(inner) = weak.upgrade()
+                        && let Ok(inner) = inner.try_lock()
+                    {
+                        let _ = inner.sync_bounds(view_id);
+                    }
+                }),
+            )
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Request a bounds sync on the next main run loop iteration
+    ///
+    /// Multiple requests made before the run loop turns are collapsed into a
+    /// single update, avoiding redundant layout work during live resize.
+    pub fn request_bounds_sync(&self, view_id: i32) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            let schedule = self.inner.lock().unwrap().queue_bounds_sync(view_id)?;
+            if schedule {
+                let weak = Arc::downgrade(&self.inner);
+                crate::macos::dispatch_main_async(move || {
+                    if let Some(inner) = weak.upgrade() {
+                        inner.lock().unwrap().flush_bounds_sync();
+                    }
+                });
+            }
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Check whether a view is layer-backed
+    pub fn is_layer_backed(&self, view_id: i32) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().is_layer_backed(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Override a view's appearance, or pass `None` to inherit it again
+    pub fn set_appearance(&self, view_id: i32, appearance: Option<Appearance>) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_appearance(view_id, appearance)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Pin a view to its current effective appearance
+    ///
+    /// The view stops following appearance changes of its window and ancestors
+    /// until [`set_appearance`](Self::set_appearance) is called with `None`.
+    /// Returns the appearance it was frozen to.
+    pub fn freeze_appearance(&self, view_id: i32) -> Result<Appearance> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().freeze_appearance(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the alpha value of a view (clamped to 0.0..=1.0)
+    pub fn set_alpha(&self, view_id: i32, alpha: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_alpha(view_id, alpha)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Animate a view's alpha over `duration` seconds (clamped to 0.0..=1.0)
+    ///
+    /// The change is instant while Reduce Motion is honored and enabled.
+    pub fn set_alpha_animated(&self, view_id: i32, alpha: f64, duration: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .animate_alpha(view_id, alpha, duration, || {})
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Like [`set_alpha_animated`](Self::set_alpha_animated), resolving when the
+    /// animation finishes
+    ///
+    /// The animation starts immediately, before the future is first polled.
+    #[cfg(feature = "async")]
+    pub fn set_alpha_animated_async(
+        &self,
+        view_id: i32,
+        alpha: f64,
+        duration: f64,
+    ) -> Result<crate::completion::Completion> {
+        #[cfg(target_os = "macos")]
+        {
+            let (completer, completion) = crate::completion::completion();
+            self.inner
+                .lock()
+                .unwrap()
+                .animate_alpha(view_id, alpha, duration, move || completer.complete())?;
+            Ok(completion)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the opacity of a view's backing layer (clamped to 0.0..=1.0)
+    ///
+    /// Unlike [`set_alpha`](Self::set_alpha) this is purely visual: it does not
+    /// affect event handling and is cheaper to animate.
+    pub fn set_layer_opacity(&self, view_id: i32, opacity: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_layer_opacity(view_id, opacity)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Get the current alpha value of a view
+    pub fn get_alpha(&self, view_id: i32) -> Result<f64> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().get_alpha(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Set the emphasized state for a view
+    ///
+    /// Applies to the NSVisualEffectView fallback. Native glass views that do
+    /// not expose an emphasized state ignore the call and log a warning.
+    pub fn set_emphasized(&self, view_id: i32, emphasized: bool) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_emphasized(view_id, emphasized)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Round the corners of the window containing a view
+    ///
+    /// An alternative to `corner_radius` that clips the window's content view
+    /// instead of masking the glass layer, leaving the blur itself unmasked.
+    pub fn round_window_corners(&self, view_id: i32, radius: f64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().round_window_corners(view_id, radius)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Number of views currently managed
+    pub fn view_count(&self) -> usize {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().view_count()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            0
+        }
+    }
+
+    /// Estimate the memory held by all managed views
+    ///
+    /// Sums, per view, its pixel area × backing scale² × 4 bytes × the number
+    /// of view-sized layers (glass, background, shadow, tint layers, grain and
+    /// so on). See [`FootprintEstimate`] for what this does not capture.
+    /// Always empty on other platforms.
+    pub fn estimated_footprint(&self) -> FootprintEstimate {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().estimated_footprint()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            FootprintEstimate::default()
+        }
+    }
+
+    /// Whether the view's window is the key window
+    ///
+    /// Returns `false` if the view is not in a window yet.
+    pub fn is_window_key(&self, view_id: i32) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().is_window_key(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Whether the view's window is the main window
+    ///
+    /// Returns `false` if the view is not in a window yet.
+    pub fn is_window_main(&self, view_id: i32) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().is_window_main(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Call `on_key` whenever the view's window becomes (`true`) or resigns
+    /// (`false`) key
+    ///
+    /// The view must already be in a window. Calling this again replaces the
+    /// previous callback; [`remove_view`](Self::remove_view) removes it. Must
+    /// be called from the main thread.
+    pub fn set_key_state_callback<F>(&self, view_id: i32, on_key: F) -> Result<()>
+    where
+        F: Fn(bool) + 'static,
+    {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .set_key_state_callback(view_id, Box::new(on_key))
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Call `on_begin` when the view's window starts a live resize and
+    /// `on_end` when it finishes
+    ///
+    /// Useful for dropping to cheaper rendering while the user drags, e.g.
+    /// [`freeze`](Self::freeze) in `on_begin` and [`unfreeze`](Self::unfreeze)
+    /// in `on_end`. The view must already be in a window. Calling this again
+    /// replaces the previous callbacks; [`remove_view`](Self::remove_view)
+    /// removes them. Must be called from the main thread.
+    pub fn set_live_resize_callbacks<B, E>(&self, view_id: i32, on_begin: B, on_end: E) -> Result<()>
+    where
+        B: Fn() + 'static,
+        E: Fn() + 'static,
+    {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_live_resize_callbacks(
+                view_id,
+                Box::new(on_begin),
+                Box::new(on_end),
+            )
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Call `on_change` whenever this view's effective appearance switches
+    /// between light and dark
+    ///
+    /// Observes the view's own `effectiveAppearance` with key-value
+    /// observing, so it also catches changes from an ancestor's appearance
+    /// override, not just the system setting. Calling this again replaces
+    /// the previous callback; [`remove_view`](Self::remove_view) removes it.
+    /// Must be called from the main thread.
+    pub fn set_effective_appearance_callback<F>(&self, view_id: i32, on_change: F) -> Result<()>
+    where
+        F: Fn(Appearance) + 'static,
+    {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner
+                .lock()
+                .unwrap()
+                .set_effective_appearance_callback(view_id, Box::new(on_change))
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Pause or resume a view's observer callbacks
+    ///
+    /// While paused, callbacks installed by
+    /// [`set_effective_appearance_callback`](Self::set_effective_appearance_callback),
+    /// [`set_key_state_callback`](Self::set_key_state_callback),
+    /// [`set_live_resize_callbacks`](Self::set_live_resize_callbacks) and
+    /// [`auto_track_window`](Self::auto_track_window) are skipped, including
+    /// ones installed during the pause. Skipped notifications are not replayed
+    /// on resume, so handle a system-wide change once yourself (e.g. with
+    /// [`reapply`](Self::reapply)) before resuming. Observers stay installed.
+    pub fn set_observers_enabled(&self, view_id: i32, enabled: bool) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_observers_enabled(view_id, enabled)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// List the glass-related selectors the running macOS exposes
+    ///
+    /// Enumerates the methods of `NSGlassEffectView` (or the fallback class)
+    /// and its superclasses below `NSView`, keeping names containing "scrim",
+    /// "variant", "subdued" or "glass". Each entry is `Class -selector`. A
+    /// diagnostic for finding private properties on new OS versions.
+    pub fn dump_glass_selectors(&self) -> Vec<String> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().dump_glass_selectors()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Vec::new()
+        }
+    }
+
+    /// Exercise the whole pipeline against a hidden offscreen window
+    ///
+    /// Creates a glass view, applies a tint, variant, scrim and subdued
+    /// state, reads each back from the native view, then removes the view
+    /// and closes the window. Each capability passes or fails on its own, so
+    /// the report shows exactly what works on the running machine. The probe
+    /// view ignores this manager's policies, environment defaults and debug
+    /// overlay, and takes no view ID from it. Must be called from the main
+    /// thread.
+    pub fn self_test(&self) -> SelfTestReport {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().self_test()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            SelfTestReport {
+                failures: vec![GlassError::UnsupportedPlatform.to_string()],
+                ..Default::default()
+            }
+        }
+    }
+
+    /// Describe the material state the native view reports
+    ///
+    /// Best-effort: reads whichever of the view's public and private material
+    /// properties (variant, scrim and subdued state, material, blending mode,
+    /// ...) the running macOS exposes, plus the effective appearance and
+    /// backing layer class, e.g. `NSGlassEffectView variant=2 scrimState=0
+    /// appearance=NSAppearanceNameDarkAqua layer=...`. The format is for
+    /// debugging and not stable.
+    pub fn effective_material(&self, view_id: i32) -> Result<String> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().effective_material(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Find the glass view previously added to a container handle
+    ///
+    /// Separators from [`add_separator`](Self::add_separator) are skipped. If
+    /// several views share the container, the lowest ID is returned.
+    pub fn view_for_handle(&self, handle: *mut std::ffi::c_void) -> Option<i32> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().view_for_handle(handle)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            None
+        }
+    }
+
+    /// Start recording a [`GlassBatch`] to apply with one lock and transaction
+    pub fn batch(&self) -> GlassBatch {
+        GlassBatch::new()
+    }
+
+    /// Apply the operations of a [`GlassBatch`]
+    fn commit_batch(&self, ops: Vec<BatchOp>) -> Vec<Result<Option<i32>>> {
+        #[cfg(target_os = "macos")]
+        {
+            let mut inner = self.inner.lock().unwrap();
+            if let Err(err) = inner.check_main_thread() {
+                return ops.iter().map(|_| Err(err.clone())).collect();
+            }
+
+            crate::macos::begin_transaction();
+            let mut results: Vec<Result<Option<i32>>> = Vec::with_capacity(ops.len());
+            for op in ops {
+                let resolve = |target: BatchTarget| match target {
+                    BatchTarget::View(view_id) => Ok(view_id),
+                    BatchTarget::Added(index) => match results.get(index) {
+                        Some(Ok(Some(view_id))) => Ok(*view_id),
+                        _ => Err(GlassError::InvalidState(format!(
+                            "batch operation {} did not add a view",
+                            index
+                        ))),
+                    },
+                };
+                let result = match op {
+                    BatchOp::Add(handle, options) => {
+                        if handle.is_null() {
+                            Err(GlassError::InvalidHandle)
+                        } else {
+                            inner.add_glass_view(handle, *options).map(Some)
+                        }
+                    }
+                    BatchOp::SetVariant(target, variant) => resolve(target)
+                        .and_then(|view_id| inner.set_variant(view_id, variant))
+                        .map(|_| None),
+                    BatchOp::SetScrimState(target, state) => resolve(target)
+                        .and_then(|view_id| inner.set_scrim_state(view_id, state))
+                        .map(|_| None),
+                    BatchOp::SetSubduedState(target, state) => resolve(target)
+                        .and_then(|view_id| inner.set_subdued_state(view_id, state))
+                        .map(|_| None),
+                    BatchOp::SetCornerRadius(target, radius) => resolve(target)
+                        .and_then(|view_id| inner.set_corner_radius(view_id, radius))
+                        .map(|_| None),
+                    BatchOp::Remove(target) => resolve(target)
+                        .and_then(|view_id| inner.remove_view(view_id))
+                        .map(|_| None),
+                };
+                results.push(result);
+            }
+            crate::macos::commit_transaction();
+            results
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            ops.iter().map(|_| Err(GlassError::UnsupportedPlatform)).collect()
+        }
+    }
+
+    /// Run `f` inside a single `CATransaction` with implicit animations disabled
+    ///
+    /// Every change made through the scope lands in the same frame. The
+    /// transaction is committed even if `f` returns an error, which is then
+    /// returned. Must be called from the main thread.
+    pub fn transaction(&self, f: impl FnOnce(&TransactionScope) -> Result<()>) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::begin_transaction();
+            let result = f(&TransactionScope { manager: self });
+            crate::macos::commit_transaction();
+            result
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Regenerate corner masks and rim highlights for each view's current
+    /// `backingScaleFactor`
+    ///
+    /// Call after a window moves to a display with a different scale, so
+    /// rounded fallbacks and 1px highlights stay crisp.
+    pub fn recompute_masks(&self) {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().recompute_masks()
+        }
+    }
+
+    /// Call `f` for every managed view, in no particular order
+    ///
+    /// Runs with the manager locked and allocates nothing; `f` must not call
+    /// back into this manager.
+    pub fn for_each_view(&self, f: impl FnMut(i32, ViewInfo<'_>)) {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().for_each_view(f)
+        }
+    }
+
+    /// Remove every view matching `predicate`, returning the removed IDs in
+    /// ascending order
+    ///
+    /// Generalizes cleanup such as removing all views with an identifier
+    /// prefix, or all views whose `container` is a closing window's view.
+    /// `predicate` runs with the manager locked and must not call back into
+    /// it. Stops at the first view that fails to be removed.
+    pub fn remove_views_where<F>(&self, predicate: F) -> Result<Vec<i32>>
+    where
+        F: Fn(i32, ViewInfo<'_>) -> bool,
+    {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().remove_views_where(predicate)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Check whether two view IDs refer to the same native view
+    ///
+    /// Returns `false` if either ID is unknown.
+    pub fn same_native_view(&self, a: i32, b: i32) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().same_native_view(a, b)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            false
+        }
+    }
+
+    /// Get a copy of the options a live view is configured with
+    ///
+    /// Useful for tweaking an existing view's look and applying it to a new view.
+    pub fn options_for(&self, view_id: i32) -> Result<GlassOptions> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().options_for(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Temporarily switch every view to the solid color backend
+    ///
+    /// Useful as a "recording-safe" mode for screen capture setups that render
+    /// glass as black. Each view keeps its options and the state
+    /// [`set_backend`](Self::set_backend) carries over, which
+    /// [`resume_all`](Self::resume_all) restores along with the real effect.
+    /// Views added while suspended are not affected, and separators and
+    /// window content views are skipped. If a view cannot be switched, the
+    /// rest still are and the first error is returned.
+    pub fn suspend_all(&self) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().suspend_all()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Restore every view suspended by [`suspend_all`](Self::suspend_all)
+    pub fn resume_all(&self) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().resume_all()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Associate caller-provided data (e.g. a JS-side handle) with a view
+    pub fn set_user_data(&self, view_id: i32, data: i64) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().set_user_data(view_id, data)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Get the data previously associated with a view (0 if never set)
+    pub fn get_user_data(&self, view_id: i32) -> Result<i64> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().get_user_data(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Remove a glass view by ID
+    pub fn remove_view(&self, view_id: i32) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().remove_view(view_id)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Remove the views added to a container handle, for when the ID is lost
+    ///
+    /// Removes every glass view whose container is `handle`, leaving
+    /// separators in place; returns `GlassError::InvalidHandle` if there are
+    /// none.
+    pub fn remove_view_by_handle(&self, handle: *mut std::ffi::c_void) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.lock().unwrap().remove_view_by_handle(handle)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Export the configuration of every view as JSON
+    ///
+    /// Includes each view's ID, options, variant, scrim and subdued state.
+    #[cfg(feature = "serde")]
+    pub fn export_state(&self) -> String {
+        #[cfg(target_os = "macos")]
+        let states = self.inner.lock().unwrap().view_states();
+
+        #[cfg(not(target_os = "macos"))]
+        let states: Vec<ViewState> = Vec::new();
+
+        serde_json::to_string(&states).expect("view state is always serializable")
+    }
+
+    /// Recreate views from [`export_state`](Self::export_state) JSON on a container
+    ///
+    /// Returns the IDs of the new views, in the order they were exported. If
+    /// any view cannot be restored, the views already restored are removed
+    /// again and the error is returned.
+    #[cfg(feature = "serde")]
+    pub fn import_state(&self, json: &str, handle: *mut std::ffi::c_void) -> Result<Vec<i32>> {
+        let states: Vec<ViewState> =
+            serde_json::from_str(json).map_err(|err| GlassError::InvalidState(err.to_string()))?;
+
+        if handle.is_null() {
+            return Err(GlassError::InvalidHandle);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let mut inner = self.inner.lock().unwrap();
+            let mut view_ids = Vec::with_capacity(states.len());
+            for state in states {
+                match inner.restore_view_state(state, handle) {
+                    Ok(view_id) => view_ids.push(view_id),
+                    Err(err) => {
+                        for view_id in view_ids {
+                            let _ = inner.remove_view(view_id);
+                        }
+                        return Err(err);
+                    }
+                }
+            }
+            Ok(view_ids)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Run `f` against the inner manager if its lock is free
+    ///
+    /// Returns `Ok(false)` without running `f` if the lock is currently held.
+    #[cfg(target_os = "macos")]
+    fn try_with(
+        &self,
+        f: impl FnOnce(&mut crate::macos::MacOSGlassManager) -> Result<()>,
+    ) -> Result<bool> {
+        match self.inner.try_lock() {
+            Ok(mut inner) => f(&mut inner).map(|()| true),
+            Err(std::sync::TryLockError::WouldBlock) => Ok(false),
+            Err(err @ std::sync::TryLockError::Poisoned(_)) => panic!("{}", err),
+        }
+    }
+
+    /// Non-blocking [`set_variant`](Self::set_variant)
+    ///
+    /// Returns `Ok(true)` if applied, or `Ok(false)` if the manager was busy and
+    /// the update was skipped.
+    pub fn try_set_variant(&self, view_id: i32, variant: GlassMaterialVariant) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.try_with(|inner| inner.set_variant(view_id, variant))
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Non-blocking [`set_scrim_state`](Self::set_scrim_state)
+    pub fn try_set_scrim_state(&self, view_id: i32, state: i64) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.try_with(|inner| inner.set_scrim_state(view_id, state))
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Non-blocking [`set_subdued_state`](Self::set_subdued_state)
+    pub fn try_set_subdued_state(&self, view_id: i32, state: i64) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.try_with(|inner| inner.set_subdued_state(view_id, state))
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err(GlassError::UnsupportedPlatform)
+        }
+    }
+
+    /// Non-blocking [`set_alpha`](Self::set_alpha)
+    pub fn try_set_alpha(&self, view_id: i32, alpha: f64) -> Result<bool> {
+        #[cfg(target_os = "macos")]
+        {
+            self.try_with(|inner| inner.set_alpha(view_id, alpha))
         }
 
         #[cfg(not(target_os = "macos"))]
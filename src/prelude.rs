@@ -0,0 +1,18 @@
+//! Commonly used types
+//!
+//! ```no_run
+//! use liquid_glass_rs::prelude::*;
+//! ```
+
+pub use crate::color::recommended_text_color;
+pub use crate::error::GlassError;
+pub use crate::platform::{
+    AccessibilityPolicy, Appearance, AttachPosition, BatchTarget, BlendingMode, CornerMask,
+    DedupPolicy, FootprintEstimate, GlassBackend, GlassBatch, GlassCapabilities,
+    GlassMaterialVariant, GlassOptions, GlassViewHandle, GlassViewManager, Orientation, PinOptions,
+    Rect, RenderingEnvironment, Shadow, TintBlendMode, TintLayer, TintTiming, TransactionScope,
+    VersionInfo, ViewInfo,
+};
+#[cfg(feature = "serde")]
+pub use crate::platform::ViewState;
+pub use crate::registry::GlassRegistry;
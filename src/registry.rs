@@ -0,0 +1,171 @@
+//! Multi-window glass view registry
+//!
+//! A single [`GlassViewManager`] shared across windows, with views grouped by
+//! the container handle they were added to.
+
+use crate::error::Result;
+use crate::platform::{GlassOptions, GlassViewManager};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
+use std::rc::Rc;
+
+/// Views added to one container
+#[derive(Default)]
+struct WindowEntry {
+    views: Vec<i32>,
+    #[cfg(target_os = "macos")]
+    close_observer: Option<crate::macos::NotificationObserver>,
+}
+
+struct RegistryInner {
+    manager: GlassViewManager,
+    windows: RefCell<HashMap<usize, WindowEntry>>,
+}
+
+impl RegistryInner {
+    fn close_window(&self, handle: usize) -> Result<()> {
+        let entry = self.windows.borrow_mut().remove(&handle);
+        match entry {
+            Some(entry) => self.remove_views(handle, &entry.views),
+            None => Ok(()),
+        }
+    }
+
+    fn remove_views(&self, handle: usize, views: &[i32]) -> Result<()> {
+        let mut result = Ok(());
+        for view_id in self.live_views(handle, views) {
+            if let Err(err) = self.manager.remove_view(view_id) {
+                result = Err(err);
+            }
+        }
+        result
+    }
+
+    /// The IDs in `views` that the manager still has in the container `handle`
+    ///
+    /// Views removed through the manager directly or replaced by the dedup
+    /// policy leave stale IDs behind, which with ID recycling may even name a
+    /// view in another container.
+    fn live_views(&self, handle: usize, views: &[i32]) -> Vec<i32> {
+        let mut in_container = HashSet::new();
+        self.manager.for_each_view(|view_id, info| {
+            if info.container as usize == handle {
+                in_container.insert(view_id);
+            }
+        });
+        views.iter().copied().filter(|view_id| in_container.contains(view_id)).collect()
+    }
+}
+
+/// Registry owning one glass manager for several windows
+///
+/// Views are namespaced per container handle. On macOS, a window's views are
+/// removed automatically when the window closes.
+pub struct GlassRegistry {
+    inner: Rc<RegistryInner>,
+}
+
+impl GlassRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(RegistryInner {
+                manager: GlassViewManager::new(),
+                windows: RefCell::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// The shared manager, for per-view setters
+    pub fn manager(&self) -> &GlassViewManager {
+        &self.inner.manager
+    }
+
+    /// Get a scope for the views of one container (NSView* on macOS)
+    pub fn window(&self, handle: *mut c_void) -> WindowScope<'_> {
+        WindowScope {
+            registry: self,
+            handle,
+        }
+    }
+
+    /// Remove every view added to a container
+    pub fn close_window(&self, handle: *mut c_void) -> Result<()> {
+        self.inner.close_window(handle as usize)
+    }
+}
+
+impl Default for GlassRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Views of a [`GlassRegistry`] belonging to a single container
+pub struct WindowScope<'a> {
+    registry: &'a GlassRegistry,
+    handle: *mut c_void,
+}
+
+impl WindowScope<'_> {
+    /// Add a glass view to this container
+    pub fn add_glass_view(&self, options: GlassOptions) -> Result<i32> {
+        let inner = &self.registry.inner;
+        let view_id = inner.manager.add_glass_view(self.handle, options)?;
+
+        let key = self.handle as usize;
+        let mut windows = inner.windows.borrow_mut();
+        let entry = windows.entry(key).or_default();
+        entry.views.push(view_id);
+
+        // Retried on every add until the container is in a window
+        #[cfg(target_os = "macos")]
+        if entry.close_observer.is_none() {
+            let weak = Rc::downgrade(inner);
+            entry.close_observer = crate::macos::NotificationObserver::window_will_close(
+                self.handle,
+                move || {
+                    let Some(inner) = weak.upgrade() else {
+                        return;
+                    };
+                    let entry = inner.windows.borrow_mut().remove(&key);
+                    if let Some(entry) = entry {
+                        let _ = inner.remove_views(key, &entry.views);
+                        // The entry owns the observer running this block, so
+                        // drop it only once the block has returned
+                        crate::macos::dispatch_main_async(move || drop(entry));
+                    }
+                },
+            );
+        }
+
+        Ok(view_id)
+    }
+
+    /// IDs of the views added to this container that are still managed
+    pub fn views(&self) -> Vec<i32> {
+        self.registry
+            .inner
+            .windows
+            .borrow()
+            .get(&(self.handle as usize))
+            .map(|entry| self.registry.inner.live_views(self.handle as usize, &entry.views))
+            .unwrap_or_default()
+    }
+
+    /// Remove a single view from this container
+    pub fn remove_view(&self, view_id: i32) -> Result<()> {
+        let inner = &self.registry.inner;
+        inner.manager.remove_view(view_id)?;
+        if let Some(entry) = inner.windows.borrow_mut().get_mut(&(self.handle as usize)) {
+            entry.views.retain(|&id| id != view_id);
+        }
+        Ok(())
+    }
+
+    /// Remove every view added to this container
+    pub fn close(self) -> Result<()> {
+        self.registry.close_window(self.handle)
+    }
+}